@@ -12,9 +12,10 @@
 //! ```
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use off_rs::geometry::color_format::ColorFormat;
-use off_rs::parser::options::Options;
+use off_rs::parser::color_format::ColorFormat;
+use off_rs::parser::options::{Limits, Options};
 use off_rs::parser::Parser;
+use std::fmt::Write;
 
 /// OFF file from wikipedia.
 const WIKI_OFF: &str = include_str!("resources/wiki.off");
@@ -44,5 +45,110 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+/// Generates a grid of `size` x `size` quads with vertex colors.
+fn generate_off(size: usize) -> String {
+    let mut off = String::from("OFF\n");
+    let _ = writeln!(off, "{} {} 0", size * size, (size - 1) * (size - 1));
+
+    for y in 0..size {
+        for x in 0..size {
+            let _ = writeln!(off, "{x}.25 {y}.5 {}.125 0.5 0.25 1.0 1.0", (x + y) % 7);
+        }
+    }
+
+    for y in 0..size - 1 {
+        for x in 0..size - 1 {
+            let i = y * size + x;
+            let _ = writeln!(off, "4 {} {} {} {}", i, i + 1, i + size + 1, i + size);
+        }
+    }
+
+    off
+}
+
+/// Options for large generated meshes.
+fn large_options() -> Options {
+    Options {
+        color_format: ColorFormat::RGBAFloat,
+        limits: Limits::MAX,
+    }
+}
+
+pub fn file_benchmark(c: &mut Criterion) {
+    let path = std::env::temp_dir().join(format!("off-rs-bench-{}.off", std::process::id()));
+    std::fs::write(&path, generate_off(500)).unwrap();
+
+    let mut group = c.benchmark_group("load 500x500 grid");
+    group.sample_size(10);
+
+    group.bench_function("from_path", |b| {
+        b.iter(|| black_box(off_rs::from_path(&path, large_options())))
+    });
+
+    #[cfg(feature = "mmap")]
+    group.bench_function("from_path_mmap", |b| {
+        b.iter(|| black_box(off_rs::from_path_mmap(&path, large_options())))
+    });
+
+    group.finish();
+    let _ = std::fs::remove_file(path);
+}
+
+pub fn large_benchmark(c: &mut Criterion) {
+    let off = generate_off(500);
+
+    let mut group = c.benchmark_group("parse 500x500 grid");
+    group.sample_size(10);
+
+    group.bench_function("serial", |b| {
+        b.iter(|| black_box(Parser::new(&off, large_options()).parse()))
+    });
+
+    #[cfg(feature = "rayon")]
+    group.bench_function("parallel", |b| {
+        b.iter(|| black_box(Parser::new(&off, large_options()).parse_parallel()))
+    });
+
+    group.finish();
+}
+
+pub fn tokenizer_benchmark(c: &mut Criterion) {
+    let off = generate_off(500);
+    let lines: Vec<&str> = off.lines().skip(2).take(500 * 500).collect();
+
+    let mut group = c.benchmark_group("tokenize 500x500 grid vertices");
+    group.sample_size(10);
+
+    // The line handling before the hand-written tokenizer: collect the parts, then parse them with the standard library
+    group.bench_function("split_line + str::parse", |b| {
+        b.iter(|| {
+            for line in &lines {
+                let parts: Vec<&str> = line
+                    .split_whitespace()
+                    .map_while(|s| (!s.starts_with('#')).then_some(s))
+                    .collect();
+                let values: Result<Vec<f32>, _> = parts.into_iter().map(str::parse).collect();
+                black_box(values.unwrap());
+            }
+        });
+    });
+
+    group.bench_function("Tokens + parse_fast", |b| {
+        b.iter(|| {
+            for line in &lines {
+                black_box(off_rs::parser::parse_floats(line).unwrap());
+            }
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    file_benchmark,
+    large_benchmark,
+    tokenizer_benchmark
+);
 criterion_main!(benches);
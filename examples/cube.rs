@@ -1,17 +1,12 @@
-use std::path::Path;
+use off_rs::{parser::color_format::ColorFormat, primitives};
 
-use off_rs::parser::{color_format::ColorFormat, options::Options};
+fn main() {
+    let cube = primitives::cube(1.0);
 
-const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/cube.off");
+    let off_string = off_rs::to_string(&cube, ColorFormat::default()).unwrap();
+    println!("{}", off_string);
 
-fn main() {
-    let mesh = off_rs::from_path(
-        Path::new(PATH),
-        Options {
-            color_format: ColorFormat::RGBAFloat,
-            ..Default::default()
-        },
-    );
+    let mesh = off_rs::parse(&off_string, Default::default());
 
     println!("{:#?}", mesh);
 }
@@ -0,0 +1,22 @@
+//! A fuzz case which checks that the hand-written tokenizer and number parsing
+//! of the parser return the same results as the standard library.
+//!
+//! # Run
+//!
+//! ```bash
+//! cargo fuzz run tokenizer
+//! ```
+
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use off_rs::parser::check_parity;
+
+// Creates a new fuzz case which accepts random bytes as input.
+fuzz_target!(|data: &[u8]| {
+    // Tries to interpret the random bytes as a single line.
+    if let Ok(s) = std::str::from_utf8(data) {
+        if let Some(line) = s.lines().next() {
+            check_parity(line);
+        }
+    }
+});
@@ -0,0 +1,352 @@
+//! Command line tool for inspecting, validating and converting `.off` files.
+//!
+//! # Run
+//!
+//! ```bash
+//! cargo run --features cli -- info examples/cube.off
+//! cargo run --features cli -- validate examples/cube.off
+//! cargo run --features cli -- convert examples/cube.off cube.stl
+//! ```
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use off_rs::{
+    compression::{self, Compression},
+    geometry::mesh::Mesh,
+    parser::{
+        color_format::ColorFormat,
+        options::{Limits, Options},
+    },
+    stl,
+};
+
+/// Inspect, validate and convert `.off` files.
+#[derive(Debug, Parser)]
+#[command(name = "off", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    #[command(flatten)]
+    options: OptionArgs,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Prints counts, bounding box, color presence and header variant of a file.
+    Info {
+        /// The file to inspect.
+        file: PathBuf,
+    },
+    /// Checks whether a file can be parsed and reports the first problem.
+    ///
+    /// Exits with a non-zero exit code if the file is invalid.
+    Validate {
+        /// The file to validate.
+        file: PathBuf,
+    },
+    /// Converts a file into another format chosen by the file extensions (`off` or `stl`).
+    ///
+    /// Files with an additional compression extension (e.g. `.off.gz`) are compressed.
+    Convert {
+        /// The file to read.
+        input: PathBuf,
+        /// The file to write.
+        output: PathBuf,
+        /// The color format of written `off` files (defaults to `--color-format`).
+        #[arg(long, value_enum)]
+        output_color_format: Option<ColorFormatArg>,
+        /// The encoding of written `stl` files.
+        #[arg(long, value_enum, default_value_t = StlFormatArg::Binary)]
+        stl_format: StlFormatArg,
+    },
+}
+
+/// Mirrors [`Options`] and [`Limits`].
+#[derive(Debug, Args)]
+struct OptionArgs {
+    /// The color format used to parse `off` files.
+    #[arg(long, global = true, value_enum, default_value_t = ColorFormatArg::Float)]
+    color_format: ColorFormatArg,
+    /// The maximum amount of vertices.
+    #[arg(long, global = true, default_value_t = Limits::default().vertex_count)]
+    max_vertices: usize,
+    /// The maximum amount of faces.
+    #[arg(long, global = true, default_value_t = Limits::default().face_count)]
+    max_faces: usize,
+    /// The maximum amount of vertices per face.
+    #[arg(long, global = true, default_value_t = Limits::default().face_vertex_count)]
+    max_face_vertices: usize,
+    /// The maximum total amount of face vertex indices.
+    #[arg(long, global = true, default_value_t = Limits::default().face_index_count)]
+    max_face_indices: usize,
+    /// The maximum size of an `off` file in bytes.
+    #[arg(long, global = true, default_value_t = Limits::default().byte_count)]
+    max_bytes: usize,
+    /// The maximum length of a line in bytes.
+    #[arg(long, global = true, default_value_t = Limits::default().line_length)]
+    max_line_length: usize,
+    /// The maximum amount of tokens per line.
+    #[arg(long, global = true, default_value_t = Limits::default().token_count)]
+    max_tokens: usize,
+    /// Disables all limits (overrides the other limit flags).
+    #[arg(long, global = true)]
+    no_limits: bool,
+}
+
+impl From<&OptionArgs> for Options {
+    fn from(args: &OptionArgs) -> Self {
+        let limits = if args.no_limits {
+            Limits::MAX
+        } else {
+            Limits {
+                vertex_count: args.max_vertices,
+                face_count: args.max_faces,
+                face_vertex_count: args.max_face_vertices,
+                face_index_count: args.max_face_indices,
+                byte_count: args.max_bytes,
+                line_length: args.max_line_length,
+                token_count: args.max_tokens,
+            }
+        };
+
+        Options {
+            color_format: args.color_format.into(),
+            limits,
+        }
+    }
+}
+
+/// Mirrors [`ColorFormat`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum ColorFormatArg {
+    RgbFloat,
+    RgbaFloat,
+    RgbInteger,
+    RgbaInteger,
+    Float,
+    Integer,
+    Auto,
+}
+
+impl From<ColorFormatArg> for ColorFormat {
+    fn from(arg: ColorFormatArg) -> Self {
+        match arg {
+            ColorFormatArg::RgbFloat => ColorFormat::RGBFloat,
+            ColorFormatArg::RgbaFloat => ColorFormat::RGBAFloat,
+            ColorFormatArg::RgbInteger => ColorFormat::RGBInteger,
+            ColorFormatArg::RgbaInteger => ColorFormat::RGBAInteger,
+            ColorFormatArg::Float => ColorFormat::Float,
+            ColorFormatArg::Integer => ColorFormat::Integer,
+            ColorFormatArg::Auto => ColorFormat::Auto,
+        }
+    }
+}
+
+/// Mirrors [`stl::Format`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum StlFormatArg {
+    Ascii,
+    Binary,
+}
+
+impl From<StlFormatArg> for stl::Format {
+    fn from(arg: StlFormatArg) -> Self {
+        match arg {
+            StlFormatArg::Ascii => stl::Format::Ascii,
+            StlFormatArg::Binary => stl::Format::Binary,
+        }
+    }
+}
+
+/// The file formats the tool can read and write.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum FileFormat {
+    Off,
+    Stl,
+}
+
+impl FileFormat {
+    /// Chooses the file format based on the extension of `path`, ignoring a compression extension.
+    fn from_path(path: &Path) -> Result<Self, String> {
+        let name = match Compression::from_path(path) {
+            Compression::None => path,
+            _ => Path::new(path.file_stem().unwrap_or_default()),
+        };
+
+        let extension = name
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match extension.as_deref() {
+            Some("off") => Ok(Self::Off),
+            Some("stl") => Ok(Self::Stl),
+            _ => Err(format!(
+                "{}: unsupported file extension (expected: `off` or `stl`, optionally compressed)",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// Prefixes an error with the path of the file it belongs to.
+fn with_path<E: Display>(path: &Path) -> impl Fn(E) -> String + '_ {
+    move |err| format!("{}: {err}", path.display())
+}
+
+/// A parsed file together with details about its encoding.
+struct Loaded {
+    mesh: Mesh,
+    /// The header of an `off` file or the encoding of an `stl` file.
+    format: String,
+    /// The color format used to parse an `off` file.
+    color_format: Option<ColorFormat>,
+}
+
+/// Reads and parses a file based on its extension.
+///
+/// The file is read once and at most `--max-bytes` bytes of decompressed data are read into memory, like
+/// [`off_rs::from_path`] does.
+fn load(path: &Path, options: Options) -> Result<Loaded, String> {
+    let file_format = FileFormat::from_path(path)?;
+    let bytes =
+        compression::read_limited(path, options.limits.byte_count).map_err(with_path(path))?;
+
+    match file_format {
+        FileFormat::Off => {
+            let string = std::str::from_utf8(&bytes).map_err(with_path(path))?;
+            let (mesh, color_format) = off_rs::parser::Parser::new(&string, options)
+                .parse_with_color_format()
+                .map_err(with_path(path))?;
+
+            Ok(Loaded {
+                mesh,
+                format: off_header(string).to_string(),
+                color_format: Some(color_format),
+            })
+        }
+        FileFormat::Stl => {
+            let mesh = stl::parse(&bytes).map_err(with_path(path))?;
+            let format = match stl::detect_format(&bytes) {
+                stl::Format::Ascii => "STL (ascii)",
+                stl::Format::Binary => "STL (binary)",
+            };
+
+            Ok(Loaded {
+                mesh,
+                format: format.to_string(),
+                color_format: None,
+            })
+        }
+    }
+}
+
+/// Returns the first non-comment line of `off` data, which the parser reads as the header.
+fn off_header(string: &str) -> &str {
+    string
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+}
+
+fn info(path: &Path, options: Options) -> Result<(), String> {
+    let Loaded {
+        mesh,
+        format,
+        color_format,
+    } = load(path, options)?;
+
+    let vertex_colors = mesh.vertices.iter().filter(|v| v.color.is_some()).count();
+    let face_colors = mesh.faces.iter().filter(|f| f.color.is_some()).count();
+
+    println!("File:          {}", path.display());
+    println!("Format:        {format}");
+    println!("Vertices:      {}", mesh.vertex_count());
+    println!("Faces:         {}", mesh.face_count());
+    println!("Edges:         {}", mesh.edge_count());
+
+    if let Some((min, max)) = mesh.bounding_box() {
+        println!("Bounding box:  ({}, {}, {})", min.x, min.y, min.z);
+        println!("               ({}, {}, {})", max.x, max.y, max.z);
+    } else {
+        println!("Bounding box:  none");
+    }
+
+    println!("Vertex colors: {vertex_colors} of {}", mesh.vertex_count());
+    println!("Face colors:   {face_colors} of {}", mesh.face_count());
+
+    if let Some(color_format) = color_format {
+        println!("Color format:  {color_format:?}");
+    }
+
+    Ok(())
+}
+
+fn validate(path: &Path, options: Options) -> Result<(), String> {
+    let mesh = load(path, options)?.mesh;
+
+    println!(
+        "{}: valid ({} vertices, {} faces)",
+        path.display(),
+        mesh.vertex_count(),
+        mesh.face_count()
+    );
+
+    Ok(())
+}
+
+fn convert(
+    input: &Path,
+    output: &Path,
+    options: Options,
+    color_format: ColorFormat,
+    stl_format: stl::Format,
+) -> Result<(), String> {
+    let output_format = FileFormat::from_path(output)?;
+    let mesh = load(input, options)?.mesh;
+
+    match output_format {
+        // The errors of `off_rs::to_path` already contain the path
+        FileFormat::Off => {
+            off_rs::to_path(&mesh, output, color_format).map_err(|err| err.to_string())
+        }
+        FileFormat::Stl => stl::to_path(&mesh, output, stl_format).map_err(with_path(output)),
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let options = Options::from(&cli.options);
+
+    let result = match &cli.command {
+        Command::Info { file } => info(file, options),
+        Command::Validate { file } => validate(file, options),
+        Command::Convert {
+            input,
+            output,
+            output_color_format,
+            stl_format,
+        } => convert(
+            input,
+            output,
+            options,
+            output_color_format.map_or(options.color_format, Into::into),
+            (*stl_format).into(),
+        ),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
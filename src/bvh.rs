@@ -0,0 +1,786 @@
+//! A bounding volume hierarchy over the faces of a [`Mesh`] for ray casting and closest point queries.
+//!
+//! Faces are split into triangles like [`Face::triangles`](crate::geometry::mesh::Face::triangles) does, so results
+//! refer to a face and the three vertices of the triangle within that face. Barycentric coordinates are the weights of
+//! these three vertices.
+
+use crate::geometry::{
+    mesh::Mesh,
+    position::Position,
+    validation::{self, Error, Shape},
+};
+
+/// The closest intersection of a ray with a mesh, see [`Bvh::intersect_ray`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RayHit {
+    /// The index of the hit face.
+    pub face: usize,
+    /// The vertex indices of the hit triangle within the face.
+    pub triangle: [usize; 3],
+    /// The distance from the origin of the ray to the hit.
+    pub distance: f32,
+    /// The barycentric coordinates of the hit within the triangle.
+    pub barycentric: [f32; 3],
+    /// The position of the hit.
+    pub position: Position,
+}
+
+/// The point of a mesh closest to a query point, see [`Bvh::closest_point`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ClosestPoint {
+    /// The index of the face containing the point.
+    pub face: usize,
+    /// The vertex indices of the triangle containing the point.
+    pub triangle: [usize; 3],
+    /// The distance from the query point to the closest point.
+    pub distance: f32,
+    /// The barycentric coordinates of the point within the triangle.
+    pub barycentric: [f32; 3],
+    /// The position of the closest point.
+    pub position: Position,
+}
+
+/// The maximum number of triangles in a leaf.
+const LEAF_SIZE: usize = 4;
+
+/// The unit directions of the rays used by [`Bvh::contains`], chosen to not be parallel to common faces.
+const RAY_DIRECTIONS: [[f32; 3]; 3] = [
+    [0.285_714_3, 0.428_571_43, 0.857_142_9],
+    [-0.267_261_24, 0.534_522_5, -0.801_783_7],
+    [0.872_871_6, -0.218_217_9, -0.436_435_8],
+];
+
+/// An axis-aligned bounding box.
+#[derive(Copy, Clone, Debug)]
+struct Bounds {
+    min: Position,
+    max: Position,
+}
+
+impl Bounds {
+    fn empty() -> Self {
+        Self {
+            min: Position::new(f32::INFINITY, f32::INFINITY, f32::INFINITY),
+            max: Position::new(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY),
+        }
+    }
+
+    fn include(&mut self, position: Position) {
+        self.min = self.min.min(position);
+        self.max = self.max.max(position);
+    }
+
+    fn union(mut self, other: &Self) -> Self {
+        self.include(other.min);
+        self.include(other.max);
+        self
+    }
+
+    /// Returns the axis along which the box is the largest.
+    fn longest_axis(&self) -> usize {
+        let size = self.max - self.min;
+        let size = [size.x, size.y, size.z];
+        (0..3)
+            .max_by(|&a, &b| size[a].total_cmp(&size[b]))
+            .unwrap_or(0)
+    }
+
+    /// Returns the squared distance from the point to the box, which is zero inside the box.
+    fn distance_squared(&self, point: Position) -> f32 {
+        let outside = (self.min - point)
+            .max(point - self.max)
+            .max(Position::default());
+        outside.dot(outside)
+    }
+
+    /// Returns the distance along the ray at which it enters the box, if it hits the box before `max_distance`.
+    fn intersect(&self, origin: Position, inverse: Position, max_distance: f32) -> Option<f32> {
+        let (mut near, mut far) = (0.0_f32, max_distance);
+
+        for axis in 0..3 {
+            let t1 =
+                (component(self.min, axis) - component(origin, axis)) * component(inverse, axis);
+            let t2 =
+                (component(self.max, axis) - component(origin, axis)) * component(inverse, axis);
+
+            // NaN occurs if the origin lies on a slab of a parallel ray, min and max ignore it
+            near = near.max(t1.min(t2));
+            far = far.min(t1.max(t2));
+        }
+
+        (near <= far).then_some(near)
+    }
+}
+
+/// A triangle of a face.
+#[derive(Copy, Clone, Debug)]
+struct Triangle {
+    face: usize,
+    vertices: [usize; 3],
+    positions: [Position; 3],
+}
+
+impl Triangle {
+    fn bounds(&self) -> Bounds {
+        let mut bounds = Bounds::empty();
+        for position in self.positions {
+            bounds.include(position);
+        }
+        bounds
+    }
+
+    fn centroid(&self) -> Position {
+        (self.positions[0] + self.positions[1] + self.positions[2]) * (1.0 / 3.0)
+    }
+
+    /// Returns the distance and barycentric coordinates of the intersection with a ray using the Möller-Trumbore
+    /// algorithm. Both sides of the triangle are hit.
+    fn intersect(&self, origin: Position, direction: Position) -> Option<(f32, [f32; 3])> {
+        let [first, second, third] = self.positions;
+        let (ab, ac) = (second - first, third - first);
+
+        let across = direction.cross(ac);
+        let det = ab.dot(across);
+        // Relative to the largest possible determinant, so that tiny triangles are not mistaken for parallel ones
+        if det.abs() <= f32::EPSILON * ab.length() * ac.length() * direction.length() {
+            return None;
+        }
+
+        let inverse = 1.0 / det;
+        let offset = origin - first;
+        let u = offset.dot(across) * inverse;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let along = offset.cross(ab);
+        let v = direction.dot(along) * inverse;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let distance = ac.dot(along) * inverse;
+        (distance >= 0.0).then_some((distance, [1.0 - u - v, u, v]))
+    }
+
+    /// Returns the barycentric coordinates of the point of the triangle closest to the given point.
+    ///
+    /// See "Real-Time Collision Detection" by Christer Ericson, section 5.1.5.
+    fn closest_point(&self, point: Position) -> [f32; 3] {
+        let [first, second, third] = self.positions;
+        let (ab, ac, ap) = (second - first, third - first, point - first);
+
+        let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return [1.0, 0.0, 0.0];
+        }
+
+        let bp = point - second;
+        let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+        if d3 >= 0.0 && d4 <= d3 {
+            return [0.0, 1.0, 0.0];
+        }
+
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            let v = d1 / (d1 - d3);
+            return [1.0 - v, v, 0.0];
+        }
+
+        let cp = point - third;
+        let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+        if d6 >= 0.0 && d5 <= d6 {
+            return [0.0, 0.0, 1.0];
+        }
+
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            let w = d2 / (d2 - d6);
+            return [1.0 - w, 0.0, w];
+        }
+
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+            let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+            return [0.0, 1.0 - w, w];
+        }
+
+        let denominator = 1.0 / (va + vb + vc);
+        let (v, w) = (vb * denominator, vc * denominator);
+        [1.0 - v - w, v, w]
+    }
+
+    fn interpolate(&self, [u, v, w]: [f32; 3]) -> Position {
+        self.positions[0] * u + self.positions[1] * v + self.positions[2] * w
+    }
+}
+
+/// A node of the hierarchy, either a leaf with a range of triangles or an inner node with two children.
+#[derive(Copy, Clone, Debug)]
+struct Node {
+    bounds: Bounds,
+    /// The first triangle of a leaf or the first child of an inner node, the second child follows it.
+    start: usize,
+    /// The number of triangles of a leaf, zero for inner nodes.
+    count: usize,
+}
+
+/// A bounding volume hierarchy over the triangles of a [`Mesh`].
+///
+/// The hierarchy stores a copy of the positions, so it does not borrow the mesh and has to be rebuilt after the mesh
+/// changes.
+///
+/// # Examples
+///
+/// ```rust
+/// use off_rs::{bvh::Bvh, geometry::position::Position, primitives};
+///
+/// let bvh = Bvh::new(&primitives::cube(2.0)).unwrap();
+///
+/// let hit = bvh
+///     .intersect_ray(Position::new(0.0, 0.0, 5.0), Position::new(0.0, 0.0, -1.0), f32::INFINITY)
+///     .unwrap();
+/// assert_eq!(hit.distance, 4.0);
+///
+/// let closest = bvh.closest_point(Position::new(3.0, 0.0, 0.0)).unwrap();
+/// assert_eq!(closest.position, Position::new(1.0, 0.0, 0.0));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    triangles: Vec<Triangle>,
+}
+
+impl Bvh {
+    /// Builds the hierarchy over the triangles of all faces of the mesh.
+    ///
+    /// Faces with less than three vertices are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a face references a vertex that does not exist.
+    pub fn new(mesh: &Mesh) -> Result<Self, Error> {
+        let mut triangles = Vec::with_capacity(mesh.faces.len());
+
+        for (index, face) in mesh.faces.iter().enumerate() {
+            validation::check_face(index, &face.vertices, mesh.vertices.len(), Shape::Any)?;
+
+            for vertices in face.triangles() {
+                let positions = vertices.map(|vertex| mesh.vertices[vertex].position);
+                triangles.push(Triangle {
+                    face: index,
+                    vertices,
+                    positions,
+                });
+            }
+        }
+
+        let mut bvh = Self {
+            nodes: Vec::with_capacity(2 * triangles.len() / LEAF_SIZE + 1),
+            triangles,
+        };
+
+        if !bvh.triangles.is_empty() {
+            bvh.nodes.push(bvh.leaf(0, bvh.triangles.len()));
+            bvh.split(0);
+        }
+
+        Ok(bvh)
+    }
+
+    /// Returns the number of triangles in the hierarchy.
+    #[must_use]
+    pub fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Returns the positions of the corners of all triangles, in no particular order.
+    pub(crate) fn triangle_positions(&self) -> impl Iterator<Item = [Position; 3]> + '_ {
+        self.triangles.iter().map(|triangle| triangle.positions)
+    }
+
+    /// Returns the closest intersection of the ray with the mesh within `max_distance` of its origin.
+    ///
+    /// The direction does not need to be normalized. Returns `None` if the ray does not hit the mesh or the direction
+    /// is zero.
+    #[must_use]
+    pub fn intersect_ray(
+        &self,
+        origin: Position,
+        direction: Position,
+        max_distance: f32,
+    ) -> Option<RayHit> {
+        if direction.length() == 0.0 || self.nodes.is_empty() {
+            return None;
+        }
+
+        let direction = direction.normalized();
+        let inverse = Position::new(1.0 / direction.x, 1.0 / direction.y, 1.0 / direction.z);
+        let mut closest: Option<(f32, usize, [f32; 3])> = None;
+        let mut stack = vec![0];
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let max_distance = closest.map_or(max_distance, |(distance, _, _)| distance);
+
+            if node
+                .bounds
+                .intersect(origin, inverse, max_distance)
+                .is_none()
+            {
+                continue;
+            }
+
+            if node.count > 0 {
+                for triangle in node.start..node.start + node.count {
+                    if let Some((distance, barycentric)) =
+                        self.triangles[triangle].intersect(origin, direction)
+                    {
+                        if distance <= max_distance
+                            && closest.is_none_or(|(closest, _, _)| distance < closest)
+                        {
+                            closest = Some((distance, triangle, barycentric));
+                        }
+                    }
+                }
+            } else {
+                // Visit the nearer child first to shrink the search distance early
+                let (first, second) = (node.start, node.start + 1);
+                let entry = |child: usize| {
+                    self.nodes[child]
+                        .bounds
+                        .intersect(origin, inverse, max_distance)
+                        .unwrap_or(f32::INFINITY)
+                };
+
+                if entry(first) <= entry(second) {
+                    stack.extend([second, first]);
+                } else {
+                    stack.extend([first, second]);
+                }
+            }
+        }
+
+        closest.map(|(distance, index, barycentric)| {
+            let triangle = &self.triangles[index];
+            RayHit {
+                face: triangle.face,
+                triangle: triangle.vertices,
+                distance,
+                barycentric,
+                position: triangle.interpolate(barycentric),
+            }
+        })
+    }
+
+    /// Returns the point of the mesh closest to the given point, or `None` if the mesh has no triangles.
+    #[must_use]
+    pub fn closest_point(&self, point: Position) -> Option<ClosestPoint> {
+        let mut closest: Option<(f32, usize, [f32; 3], Position)> = None;
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let best = closest.map_or(f32::INFINITY, |(distance, _, _, _)| distance);
+
+            if node.bounds.distance_squared(point) > best {
+                continue;
+            }
+
+            if node.count > 0 {
+                for index in node.start..node.start + node.count {
+                    let triangle = &self.triangles[index];
+                    let barycentric = triangle.closest_point(point);
+                    let position = triangle.interpolate(barycentric);
+                    let offset = position - point;
+                    let distance = offset.dot(offset);
+
+                    if closest.is_none_or(|(closest, _, _, _)| distance < closest) {
+                        closest = Some((distance, index, barycentric, position));
+                    }
+                }
+            } else {
+                let (first, second) = (node.start, node.start + 1);
+                let distance = |child: usize| self.nodes[child].bounds.distance_squared(point);
+
+                if distance(first) <= distance(second) {
+                    stack.extend([second, first]);
+                } else {
+                    stack.extend([first, second]);
+                }
+            }
+        }
+
+        closest.map(|(distance, index, barycentric, position)| {
+            let triangle = &self.triangles[index];
+            ClosestPoint {
+                face: triangle.face,
+                triangle: triangle.vertices,
+                distance: distance.sqrt(),
+                barycentric,
+                position,
+            }
+        })
+    }
+
+    /// Returns `true` if the point lies inside the mesh, which has to be closed.
+    ///
+    /// Counts the crossings of three rays in different directions and takes the majority vote of their parity, so a ray
+    /// that grazes an edge or vertex does not change the result. Use [`Bvh::winding_number`] for meshes with small
+    /// holes.
+    #[must_use]
+    pub fn contains(&self, point: Position) -> bool {
+        RAY_DIRECTIONS
+            .iter()
+            .filter(|&&[x, y, z]| self.count_crossings(point, Position::new(x, y, z)) % 2 == 1)
+            .count()
+            >= 2
+    }
+
+    /// Returns the generalized winding number of the mesh around the point.
+    ///
+    /// The winding number is close to one inside and close to zero outside of a closed mesh whose faces point
+    /// outwards, and degrades gracefully for meshes with holes. Unlike [`Bvh::contains`] it visits every triangle.
+    #[must_use]
+    pub fn winding_number(&self, point: Position) -> f32 {
+        let solid_angle: f32 = self
+            .triangles
+            .iter()
+            .map(|triangle| {
+                // The solid angle of the triangle by Van Oosterom and Strackee
+                let [a, b, c] = triangle.positions.map(|position| position - point);
+                let (la, lb, lc) = (a.length(), b.length(), c.length());
+                let numerator = a.dot(b.cross(c));
+                let denominator = la * lb * lc + a.dot(b) * lc + b.dot(c) * la + c.dot(a) * lb;
+                2.0 * numerator.atan2(denominator)
+            })
+            .sum();
+
+        solid_angle / (4.0 * std::f32::consts::PI)
+    }
+
+    /// Returns the distance from the point to the mesh, which is negative inside the mesh according to
+    /// [`Bvh::contains`].
+    ///
+    /// Returns `None` if the mesh has no triangles.
+    #[must_use]
+    pub fn signed_distance(&self, point: Position) -> Option<f32> {
+        let distance = self.closest_point(point)?.distance;
+        Some(if self.contains(point) {
+            -distance
+        } else {
+            distance
+        })
+    }
+
+    /// Returns the number of triangles hit by the ray.
+    fn count_crossings(&self, origin: Position, direction: Position) -> usize {
+        let inverse = Position::new(1.0 / direction.x, 1.0 / direction.y, 1.0 / direction.z);
+        let mut count = 0;
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+
+            if node
+                .bounds
+                .intersect(origin, inverse, f32::INFINITY)
+                .is_none()
+            {
+                continue;
+            }
+
+            if node.count > 0 {
+                count += self.triangles[node.start..node.start + node.count]
+                    .iter()
+                    .filter(|triangle| triangle.intersect(origin, direction).is_some())
+                    .count();
+            } else {
+                stack.extend([node.start, node.start + 1]);
+            }
+        }
+
+        count
+    }
+
+    /// Creates a leaf over the given range of triangles.
+    fn leaf(&self, start: usize, count: usize) -> Node {
+        let bounds = self.triangles[start..start + count]
+            .iter()
+            .fold(Bounds::empty(), |bounds, triangle| {
+                bounds.union(&triangle.bounds())
+            });
+
+        Node {
+            bounds,
+            start,
+            count,
+        }
+    }
+
+    /// Recursively splits a leaf at the median of its triangle centroids along the longest axis.
+    fn split(&mut self, index: usize) {
+        let Node { start, count, .. } = self.nodes[index];
+        if count <= LEAF_SIZE {
+            return;
+        }
+
+        let mut centroids = Bounds::empty();
+        for triangle in &self.triangles[start..start + count] {
+            centroids.include(triangle.centroid());
+        }
+        let axis = centroids.longest_axis();
+
+        let half = count / 2;
+        self.triangles[start..start + count].select_nth_unstable_by(half, |a, b| {
+            component(a.centroid(), axis).total_cmp(&component(b.centroid(), axis))
+        });
+
+        let first = self.nodes.len();
+        self.nodes.push(self.leaf(start, half));
+        self.nodes.push(self.leaf(start + half, count - half));
+        self.nodes[index].start = first;
+        self.nodes[index].count = 0;
+
+        self.split(first);
+        self.split(first + 1);
+    }
+}
+
+/// Returns the coordinate of the position along the given axis.
+fn component(position: Position, axis: usize) -> f32 {
+    match axis {
+        0 => position.x,
+        1 => position.y,
+        _ => position.z,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::mesh::Face, primitives};
+
+    /// Generates deterministic pseudo-random positions in the cube from -2 to 2.
+    fn positions(count: usize) -> Vec<Position> {
+        let mut state = 0x2545_f491_u32;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            f32::from(u16::try_from(state >> 16).unwrap()) / f32::from(u16::MAX) * 4.0 - 2.0
+        };
+
+        (0..count)
+            .map(|_| Position::new(next(), next(), next()))
+            .collect()
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn ray_hit() {
+        let bvh = Bvh::new(&primitives::cube(2.0)).unwrap();
+        let hit = bvh
+            .intersect_ray(
+                Position::new(0.5, 0.25, 5.0),
+                Position::new(0.0, 0.0, -2.0),
+                f32::INFINITY,
+            )
+            .unwrap();
+
+        assert_eq!(hit.face, 1);
+        assert_eq!(hit.distance, 4.0);
+        assert_eq!(hit.position, Position::new(0.5, 0.25, 1.0));
+        assert!((hit.barycentric.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+
+        // Rays from inside hit the back side of the faces
+        let hit = bvh
+            .intersect_ray(Position::default(), Position::new(1.0, 0.0, 0.0), 10.0)
+            .unwrap();
+        assert_eq!(hit.distance, 1.0);
+    }
+
+    #[test]
+    fn small_scale() {
+        let bvh = Bvh::new(&primitives::cube(1e-4)).unwrap();
+        let hit = bvh
+            .intersect_ray(
+                Position::new(0.0, 0.0, 1e-3),
+                Position::new(0.0, 0.0, -1.0),
+                f32::INFINITY,
+            )
+            .unwrap();
+
+        assert_eq!(hit.face, 1);
+        assert!((hit.distance - 9.5e-4).abs() < 1e-9);
+        assert!(bvh.contains(Position::default()));
+        assert!(!bvh.contains(Position::new(0.0, 0.0, 1e-3)));
+        assert!(bvh.signed_distance(Position::default()).unwrap() < 0.0);
+    }
+
+    #[test]
+    fn ray_miss() {
+        let bvh = Bvh::new(&primitives::cube(2.0)).unwrap();
+        let origin = Position::new(0.0, 0.0, 5.0);
+
+        assert!(bvh
+            .intersect_ray(origin, Position::new(0.0, 0.0, 1.0), f32::INFINITY)
+            .is_none());
+        assert!(bvh
+            .intersect_ray(origin, Position::new(0.0, 0.0, -1.0), 3.9)
+            .is_none());
+        assert!(bvh
+            .intersect_ray(origin, Position::default(), 10.0)
+            .is_none());
+        assert!(bvh
+            .intersect_ray(
+                Position::new(3.0, 3.0, 3.0),
+                Position::new(0.0, 0.0, -1.0),
+                f32::INFINITY
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn same_as_brute_force() {
+        let mesh = primitives::icosphere(1.0, 3);
+        let bvh = Bvh::new(&mesh).unwrap();
+        assert_eq!(bvh.triangle_count(), mesh.face_count());
+
+        let points = positions(200);
+        for pair in points.chunks(2) {
+            let (origin, direction) = (pair[0] * 2.0, pair[1]);
+
+            let expected = bvh
+                .triangles
+                .iter()
+                .filter_map(|triangle| triangle.intersect(origin, direction.normalized()))
+                .map(|(distance, _)| distance)
+                .min_by(f32::total_cmp);
+            let actual = bvh
+                .intersect_ray(origin, direction, f32::INFINITY)
+                .map(|hit| hit.distance);
+            assert_eq!(actual, expected);
+
+            let expected = bvh
+                .triangles
+                .iter()
+                .map(|triangle| {
+                    (triangle.interpolate(triangle.closest_point(origin)) - origin).length()
+                })
+                .min_by(f32::total_cmp)
+                .unwrap();
+            let actual = bvh.closest_point(origin).unwrap();
+            assert!((actual.distance - expected).abs() < 1e-5);
+            assert!((actual.position.length() - 1.0).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn closest_point() {
+        let bvh = Bvh::new(&primitives::cube(2.0)).unwrap();
+
+        let closest = bvh.closest_point(Position::new(3.0, 0.5, 0.0)).unwrap();
+        assert_eq!(closest.face, 5);
+        assert_eq!(closest.distance, 2.0);
+        assert_eq!(closest.position, Position::new(1.0, 0.5, 0.0));
+
+        let corner = bvh.closest_point(Position::new(2.0, 2.0, 2.0)).unwrap();
+        assert_eq!(corner.position, Position::new(1.0, 1.0, 1.0));
+        assert!(corner.barycentric.contains(&1.0));
+
+        let inside = bvh.closest_point(Position::new(0.0, 0.0, 0.75)).unwrap();
+        assert_eq!(inside.face, 1);
+        assert!((inside.distance - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn contains() {
+        let bvh = Bvh::new(&primitives::cube(2.0)).unwrap();
+        assert!(bvh.contains(Position::default()));
+        assert!(bvh.contains(Position::new(0.9, -0.9, 0.5)));
+        assert!(!bvh.contains(Position::new(1.1, 0.0, 0.0)));
+        assert!(!bvh.contains(Position::new(0.0, 0.0, -3.0)));
+
+        // The center of a torus lies outside of it
+        let torus = Bvh::new(&primitives::torus(2.0, 0.5, 16, 8)).unwrap();
+        assert!(!torus.contains(Position::default()));
+        assert!(torus.contains(Position::new(0.0, 2.0, 0.0)));
+    }
+
+    #[test]
+    fn winding_number() {
+        let mut mesh = primitives::icosphere(1.0, 2);
+        let bvh = Bvh::new(&mesh).unwrap();
+        assert!((bvh.winding_number(Position::default()) - 1.0).abs() < 1e-4);
+        assert!(bvh.winding_number(Position::new(0.0, 0.0, 2.0)).abs() < 1e-4);
+
+        // A small hole barely changes the winding number
+        mesh.faces.truncate(mesh.faces.len() - 1);
+        let bvh = Bvh::new(&mesh).unwrap();
+        assert!(bvh.winding_number(Position::default()) > 0.9);
+    }
+
+    #[test]
+    fn signed_distance() {
+        let bvh = Bvh::new(&primitives::cube(2.0)).unwrap();
+        let inside = bvh.signed_distance(Position::new(0.0, 0.0, 0.75)).unwrap();
+        assert!((inside + 0.25).abs() < 1e-6);
+        let outside = bvh.signed_distance(Position::new(0.0, 3.0, 0.0)).unwrap();
+        assert!((outside - 2.0).abs() < 1e-6);
+
+        assert!(Bvh::default()
+            .signed_distance(Position::default())
+            .is_none());
+    }
+
+    #[test]
+    fn polygons() {
+        let mut mesh = primitives::plane(2.0, 2.0, 1, 1);
+        mesh.faces.push(Face::new([0, 1], None));
+        let bvh = Bvh::new(&mesh).unwrap();
+        assert_eq!(bvh.triangle_count(), 2);
+
+        let hit = bvh
+            .intersect_ray(
+                Position::new(-0.5, 0.5, 1.0),
+                Position::new(0.0, 0.0, -1.0),
+                f32::INFINITY,
+            )
+            .unwrap();
+        assert_eq!(hit.face, 0);
+        assert_eq!(hit.triangle, [0, 3, 2]);
+    }
+
+    #[test]
+    fn empty() {
+        let bvh = Bvh::new(&Mesh::default()).unwrap();
+        assert!(bvh.closest_point(Position::default()).is_none());
+        assert!(bvh
+            .intersect_ray(Position::default(), Position::new(1.0, 0.0, 0.0), 1.0)
+            .is_none());
+    }
+
+    #[test]
+    fn vertex_out_of_range() {
+        let mut mesh = primitives::cube(1.0);
+        mesh.faces[2] = Face::new([0, 1, 8], None);
+        assert_eq!(
+            Bvh::new(&mesh).unwrap_err(),
+            Error::VertexOutOfRange {
+                face: 2,
+                vertex: 8,
+                vertex_count: 8
+            }
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! Transparent compression of `off` and `stl` files.
+//!
+//! Compressed files are recognized by their magic bytes when reading and by their file extension when writing.
+//! Every compression format is implemented behind an optional feature:
+//!
+//! | Format  | Feature | Extensions      |
+//! |---------|---------|-----------------|
+//! | gzip    | `gzip`  | `.gz`           |
+//! | zstd    | `zstd`  | `.zst`, `.zstd` |
+//! | bzip2   | `bzip2` | `.bz2`          |
+//!
+//! Reading or writing a format whose feature is disabled results in an [`io::Error`] of kind
+//! [`io::ErrorKind::Unsupported`].
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+/// The compression formats that can be detected.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Compression {
+    /// The data is not compressed.
+    #[default]
+    None,
+    /// The data is compressed with gzip (`.gz`).
+    Gzip,
+    /// The data is compressed with zstd (`.zst`).
+    Zstd,
+    /// The data is compressed with bzip2 (`.bz2`).
+    Bzip2,
+}
+
+impl Compression {
+    /// Detects the compression format by the magic bytes at the start of `bytes`.
+    #[must_use]
+    pub fn detect(bytes: &[u8]) -> Self {
+        match bytes {
+            [0x1F, 0x8B, ..] => Compression::Gzip,
+            [0x28, 0xB5, 0x2F, 0xFD, ..] => Compression::Zstd,
+            [b'B', b'Z', b'h', b'1'..=b'9', ..] => Compression::Bzip2,
+            _ => Compression::None,
+        }
+    }
+
+    /// Chooses the compression format based on the extension of `path` (e.g. `mesh.off.gz`).
+    #[must_use]
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match extension.as_deref() {
+            Some("gz") => Compression::Gzip,
+            Some("zst" | "zstd") => Compression::Zstd,
+            Some("bz2") => Compression::Bzip2,
+            _ => Compression::None,
+        }
+    }
+
+    /// Returns whether the feature implementing the compression format is enabled.
+    #[must_use]
+    pub fn is_supported(self) -> bool {
+        match self {
+            Compression::None => true,
+            Compression::Gzip => cfg!(feature = "gzip"),
+            Compression::Zstd => cfg!(feature = "zstd"),
+            Compression::Bzip2 => cfg!(feature = "bzip2"),
+        }
+    }
+
+    /// Returns the name of the feature implementing the compression format.
+    fn feature(self) -> &'static str {
+        match self {
+            Compression::None => "",
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+            Compression::Bzip2 => "bzip2",
+        }
+    }
+
+    /// Returns an [`io::Error`] for a compression format whose feature is disabled.
+    fn unsupported(self) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "{self:?} compressed data requires the `{}` feature",
+                self.feature()
+            ),
+        )
+    }
+
+    /// Wraps `reader` into a reader returning the decompressed data.
+    ///
+    /// Data consisting of multiple concatenated members or frames is decompressed completely.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`io::Error`] if the feature of the compression format is disabled or the decoder cannot be created.
+    pub fn decoder<'a, R: BufRead + 'a>(self, reader: R) -> io::Result<Box<dyn Read + 'a>> {
+        match self {
+            Compression::None => Ok(Box::new(reader)),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Ok(Box::new(flate2::bufread::MultiGzDecoder::new(reader))),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Ok(Box::new(zstd::stream::read::Decoder::with_buffer(reader)?)),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => Ok(Box::new(bzip2::bufread::MultiBzDecoder::new(reader))),
+            #[allow(unreachable_patterns)]
+            _ => Err(self.unsupported()),
+        }
+    }
+
+    /// Wraps `writer` into an [`Encoder`] compressing all written data.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`io::Error`] if the feature of the compression format is disabled or the encoder cannot be created.
+    pub fn encoder<W: Write>(self, writer: W) -> io::Result<Encoder<W>> {
+        let inner = match self {
+            Compression::None => Inner::None(writer),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Inner::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            )),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Inner::Zstd(zstd::stream::write::Encoder::new(writer, 0)?),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => Inner::Bzip2(bzip2::write::BzEncoder::new(
+                writer,
+                bzip2::Compression::default(),
+            )),
+            #[allow(unreachable_patterns)]
+            _ => return Err(self.unsupported()),
+        };
+
+        Ok(Encoder(inner))
+    }
+}
+
+/// A writer compressing the written data with a [`Compression`] format.
+///
+/// [`Encoder::finish`] has to be called after writing to complete the compressed data.
+pub struct Encoder<W: Write>(Inner<W>);
+
+/// The encoders of the supported compression formats.
+enum Inner<W: Write> {
+    None(W),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::write::BzEncoder<W>),
+}
+
+impl<W: Write> Encoder<W> {
+    /// Completes the compressed data and returns the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`io::Error`] if writing the remaining data fails.
+    pub fn finish(self) -> io::Result<W> {
+        match self.0 {
+            Inner::None(writer) => Ok(writer),
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(encoder) => encoder.finish(),
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(encoder) => encoder.finish(),
+            #[cfg(feature = "bzip2")]
+            Inner::Bzip2(encoder) => encoder.finish(),
+        }
+    }
+
+    /// Returns the writer the data is currently written to.
+    fn writer(&mut self) -> &mut dyn Write {
+        match &mut self.0 {
+            Inner::None(writer) => writer,
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(encoder) => encoder,
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(encoder) => encoder,
+            #[cfg(feature = "bzip2")]
+            Inner::Bzip2(encoder) => encoder,
+        }
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer().flush()
+    }
+}
+
+impl<W: Write> std::fmt::Debug for Encoder<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let compression = match self.0 {
+            Inner::None(_) => Compression::None,
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(_) => Compression::Gzip,
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(_) => Compression::Zstd,
+            #[cfg(feature = "bzip2")]
+            Inner::Bzip2(_) => Compression::Bzip2,
+        };
+
+        f.debug_tuple("Encoder").field(&compression).finish()
+    }
+}
+
+/// Reads the file at `path`, decompressing it if its magic bytes match a [`Compression`] format.
+///
+/// # Errors
+///
+/// Will return [`io::Error`] if reading or decompressing the file fails.
+pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    open(path)?.read_to_end(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+/// Reads the file at `path` into a [`String`], decompressing it if its magic bytes match a [`Compression`] format.
+///
+/// # Errors
+///
+/// Will return [`io::Error`] if reading or decompressing the file fails or if it is not valid UTF-8.
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    let mut string = String::new();
+    open(path)?.read_to_string(&mut string)?;
+
+    Ok(string)
+}
+
+/// Reads the file at `path` like [`read`], but stops as soon as the decompressed data exceeds `max` bytes.
+///
+/// # Errors
+///
+/// Will return [`io::Error`] if reading or decompressing the file fails, or one of kind [`io::ErrorKind::InvalidData`]
+/// if the decompressed data exceeds `max` bytes.
+pub fn read_limited<P: AsRef<Path>>(path: P, max: usize) -> io::Result<Vec<u8>> {
+    read_at_most(open(path)?, max)?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Decompressed data exceeds the limit of {max} bytes"),
+        )
+    })
+}
+
+/// Reads all data from `reader`, or returns `None` if it exceeds `max` bytes.
+///
+/// Reading stops at the first byte beyond the limit, so the rest of a decompression bomb or an endless stream is never
+/// read.
+pub(crate) fn read_at_most<R: Read>(reader: R, max: usize) -> io::Result<Option<Vec<u8>>> {
+    let mut bytes = Vec::new();
+    reader
+        .take(u64::try_from(max).unwrap_or(u64::MAX).saturating_add(1))
+        .read_to_end(&mut bytes)?;
+
+    Ok((bytes.len() <= max).then_some(bytes))
+}
+
+/// Opens the file at `path` for reading the decompressed data.
+pub(crate) fn open<P: AsRef<Path>>(path: P) -> io::Result<Box<dyn Read>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let compression = Compression::detect(reader.fill_buf()?);
+
+    compression.decoder(reader)
+}
+
+/// Creates the file at `path` for writing, compressing the data based on the extension of `path`.
+///
+/// # Errors
+///
+/// Will return [`io::Error`] if the file cannot be created or the compression format is not supported.
+pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Encoder<BufWriter<File>>> {
+    let compression = Compression::from_path(&path);
+
+    if !compression.is_supported() {
+        return Err(compression.unsupported());
+    }
+
+    compression.encoder(BufWriter::new(File::create(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect() {
+        assert_eq!(Compression::detect(b"OFF\n"), Compression::None);
+        assert_eq!(Compression::detect(b""), Compression::None);
+        assert_eq!(Compression::detect(&[0x1F, 0x8B, 0x08]), Compression::Gzip);
+        assert_eq!(
+            Compression::detect(&[0x28, 0xB5, 0x2F, 0xFD, 0x00]),
+            Compression::Zstd
+        );
+        assert_eq!(Compression::detect(b"BZh9"), Compression::Bzip2);
+        assert_eq!(Compression::detect(b"BZh"), Compression::None);
+    }
+
+    #[test]
+    fn from_path() {
+        assert_eq!(Compression::from_path("a.off"), Compression::None);
+        assert_eq!(Compression::from_path("a.off.gz"), Compression::Gzip);
+        assert_eq!(Compression::from_path("a.off.ZST"), Compression::Zstd);
+        assert_eq!(Compression::from_path("a.off.zstd"), Compression::Zstd);
+        assert_eq!(Compression::from_path("a.stl.bz2"), Compression::Bzip2);
+    }
+
+    #[test]
+    fn roundtrip() {
+        for compression in [
+            Compression::None,
+            Compression::Gzip,
+            Compression::Zstd,
+            Compression::Bzip2,
+        ] {
+            let Ok(mut encoder) = compression.encoder(Vec::new()) else {
+                assert!(!compression.is_supported());
+                continue;
+            };
+
+            encoder.write_all(b"OFF\n0 0\n").unwrap();
+            let bytes = encoder.finish().unwrap();
+            assert_eq!(Compression::detect(&bytes), compression);
+
+            let mut string = String::new();
+            compression
+                .decoder(&bytes[..])
+                .unwrap()
+                .read_to_string(&mut string)
+                .unwrap();
+            assert_eq!(string, "OFF\n0 0\n");
+        }
+    }
+
+    #[test]
+    fn unsupported() {
+        for compression in [Compression::Gzip, Compression::Zstd, Compression::Bzip2] {
+            if !compression.is_supported() {
+                let err = compression.decoder(&b""[..]).err().unwrap();
+                assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+            }
+        }
+    }
+}
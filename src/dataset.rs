@@ -0,0 +1,393 @@
+//! Batch loading of directory trees of `.off` files, such as [ModelNet](<https://modelnet.cs.princeton.edu/>) or the
+//! [Princeton Shape Benchmark](<https://shape.cs.princeton.edu/benchmark/>).
+//!
+//! Every file is parsed with the same [`Options`] and returned as an [`Entry`] with its own [`crate::Result`], so a
+//! single broken file does not abort loading the whole dataset. The category of a file is derived by the [`Layout`].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use off_rs::dataset::{self, Layout, Summary};
+//!
+//! let entries = dataset::load("ModelNet40", Default::default(), &Layout::Directory)?;
+//! let summary = Summary::from_entries(&entries);
+//!
+//! println!("{} of {} files loaded", summary.loaded_count, summary.file_count);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    compression::Compression,
+    parser::{error::Kind, options::Options},
+    Error,
+};
+
+/// Describes how the category of a file is derived from its location.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// The category is the first directory below the root, e.g. `airplane` for
+    /// `ModelNet40/airplane/train/airplane_0001.off`.
+    #[default]
+    Directory,
+    /// The category is the name of the directory containing the file.
+    ParentDirectory,
+    /// The category is looked up in a [`Classification`] by the model id of the file name, e.g. `m123.off`.
+    Classification(Classification),
+    /// Files have no category.
+    None,
+}
+
+/// The split of a dataset a file belongs to, derived from a `train` or `test` directory in its path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Split {
+    /// The file is located in a `train` directory.
+    Train,
+    /// The file is located in a `test` directory.
+    Test,
+}
+
+/// A file of a dataset and the result of parsing it.
+#[derive(Debug)]
+pub struct Entry {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The category derived by the [`Layout`].
+    pub category: Option<String>,
+    /// The split derived from the directories of the path.
+    pub split: Option<Split>,
+    /// The parsed [`crate::geometry::mesh::Mesh`] or the error that occurred.
+    pub result: crate::Result,
+}
+
+/// Maps the model ids of the Princeton Shape Benchmark to their class names.
+///
+/// The mapping is read from the `.cla` classification files which are distributed with the benchmark.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Classification {
+    classes: HashMap<String, String>,
+}
+
+impl Classification {
+    /// Parses a classification from the contents of a `.cla` file.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`io::Error`] of kind [`io::ErrorKind::InvalidData`] if the contents are not a valid `.cla` file.
+    pub fn parse(string: &str) -> io::Result<Self> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let mut lines = string
+            .lines()
+            .map(str::split_whitespace)
+            .map(Iterator::collect::<Vec<_>>)
+            .filter(|parts| !parts.is_empty());
+
+        match lines.next().as_deref() {
+            Some(["PSB", _]) => {}
+            _ => return Err(invalid("Missing `PSB` header".to_string())),
+        }
+
+        if !matches!(lines.next().as_deref(), Some([_, _])) {
+            return Err(invalid("Missing class and model counts".to_string()));
+        }
+
+        let mut classes = HashMap::new();
+
+        while let Some(parts) = lines.next() {
+            let [class, _parent, count] = parts[..] else {
+                return Err(invalid(format!(
+                    "Invalid class definition `{}`",
+                    parts.join(" ")
+                )));
+            };
+
+            let count: usize = count
+                .parse()
+                .map_err(|err| invalid(format!("Invalid model count of class `{class}`: {err}")))?;
+
+            for _ in 0..count {
+                match lines.next().as_deref() {
+                    Some([id]) => {
+                        classes.insert((*id).to_string(), class.to_string());
+                    }
+                    _ => return Err(invalid(format!("Missing model id of class `{class}`"))),
+                }
+            }
+        }
+
+        Ok(Self { classes })
+    }
+
+    /// Reads a classification from a `.cla` file.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`io::Error`] if reading the file fails or if it is not a valid `.cla` file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Returns the class name of the model with the given id.
+    #[must_use]
+    pub fn class(&self, id: &str) -> Option<&str> {
+        self.classes.get(id).map(String::as_str)
+    }
+}
+
+impl Layout {
+    /// Derives the category of the file at `path` located below `root`.
+    #[must_use]
+    pub fn category(&self, root: &Path, path: &Path) -> Option<String> {
+        let directories = || {
+            path.strip_prefix(root)
+                .unwrap_or(path)
+                .parent()
+                .into_iter()
+                .flat_map(Path::iter)
+        };
+
+        let name = match self {
+            Layout::Directory => directories().next(),
+            Layout::ParentDirectory => directories().last(),
+            Layout::Classification(classification) => {
+                let id = model_name(path)?;
+                return classification
+                    .class(id.strip_prefix('m').unwrap_or(id))
+                    .map(ToString::to_string);
+            }
+            Layout::None => None,
+        };
+
+        name.and_then(|name| name.to_str()).map(ToString::to_string)
+    }
+}
+
+/// Summary statistics of loading a dataset.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Summary {
+    /// The number of files.
+    pub file_count: usize,
+    /// The number of files which were parsed successfully.
+    pub loaded_count: usize,
+    /// The number of files which could not be read.
+    pub io_error_count: usize,
+    /// The number of files which could not be parsed by the [`Kind`] of the error.
+    pub parser_errors: HashMap<Kind, usize>,
+    /// The number of files per category.
+    pub categories: BTreeMap<String, usize>,
+}
+
+impl Summary {
+    /// Collects the statistics of the given entries.
+    #[must_use]
+    pub fn from_entries(entries: &[Entry]) -> Self {
+        let mut summary = Self::default();
+
+        for entry in entries {
+            summary.file_count += 1;
+
+            match &entry.result {
+                Ok(_) => summary.loaded_count += 1,
+                Err(Error::IOError(_)) => summary.io_error_count += 1,
+                Err(Error::ParserError(err)) => {
+                    *summary.parser_errors.entry(err.kind).or_default() += 1;
+                }
+            }
+
+            if let Some(category) = &entry.category {
+                *summary.categories.entry(category.clone()).or_default() += 1;
+            }
+        }
+
+        summary
+    }
+
+    /// Returns the number of files which could not be read or parsed.
+    #[must_use]
+    pub fn failed_count(&self) -> usize {
+        self.file_count - self.loaded_count
+    }
+}
+
+/// Returns the name of the file without the `.off` and compression extensions, or `None` if it is not an `off` file.
+fn model_name(path: &Path) -> Option<&str> {
+    let name = path.file_name()?.to_str()?;
+
+    let name = match Compression::from_path(path) {
+        Compression::None => name,
+        _ => name.rsplit_once('.')?.0,
+    };
+
+    let (stem, extension) = name.rsplit_once('.')?;
+    extension.eq_ignore_ascii_case("off").then_some(stem)
+}
+
+/// Returns the [`Split`] of the file at `path` located below `root`.
+fn split(root: &Path, path: &Path) -> Option<Split> {
+    let directories = path.strip_prefix(root).unwrap_or(path).parent()?;
+
+    directories.iter().find_map(|name| match name.to_str()? {
+        "train" => Some(Split::Train),
+        "test" => Some(Split::Test),
+        _ => None,
+    })
+}
+
+/// Returns the paths of all `.off` files (including compressed ones like `.off.gz`) below `root` in sorted order.
+///
+/// Symbolic links are not followed.
+///
+/// # Errors
+///
+/// Will return [`io::Error`] if a directory cannot be read.
+pub fn paths<P: AsRef<Path>>(root: P) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    let mut directories = vec![root.as_ref().to_path_buf()];
+
+    while let Some(directory) = directories.pop() {
+        for entry in fs::read_dir(directory)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+
+            if file_type.is_dir() {
+                directories.push(entry.path());
+            } else if file_type.is_file() && model_name(&entry.path()).is_some() {
+                paths.push(entry.path());
+            }
+        }
+    }
+
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Creates the [`Entry`] of a single file.
+fn entry(root: &Path, path: PathBuf, options: Options, layout: &Layout) -> Entry {
+    Entry {
+        category: layout.category(root, &path),
+        split: split(root, &path),
+        result: crate::from_path(&path, options),
+        path,
+    }
+}
+
+/// Parses all `.off` files below `root` with the same [`Options`].
+///
+/// The entries are returned in the sorted order of their paths.
+///
+/// # Errors
+///
+/// Will return [`io::Error`] if a directory cannot be read. Errors of individual files are returned in their [`Entry`].
+pub fn load<P: AsRef<Path>>(root: P, options: Options, layout: &Layout) -> io::Result<Vec<Entry>> {
+    let root = root.as_ref();
+
+    Ok(paths(root)?
+        .into_iter()
+        .map(|path| entry(root, path, options, layout))
+        .collect())
+}
+
+/// Parses all `.off` files below `root` like [`load`], but parses multiple files at once on multiple threads.
+///
+/// # Errors
+///
+/// Will return [`io::Error`] if a directory cannot be read. Errors of individual files are returned in their [`Entry`].
+#[cfg(feature = "rayon")]
+pub fn load_parallel<P: AsRef<Path>>(
+    root: P,
+    options: Options,
+    layout: &Layout,
+) -> io::Result<Vec<Entry>> {
+    use rayon::prelude::*;
+
+    let root = root.as_ref();
+
+    Ok(paths(root)?
+        .into_par_iter()
+        .map(|path| entry(root, path, options, layout))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLA: &str = "PSB 1\n2 3\n\nairplane 0 2\n12\n1200\n\nchair furniture 1\n7\n";
+
+    #[test]
+    fn classification() {
+        let classification = Classification::parse(CLA).unwrap();
+        assert_eq!(classification.class("12"), Some("airplane"));
+        assert_eq!(classification.class("1200"), Some("airplane"));
+        assert_eq!(classification.class("7"), Some("chair"));
+        assert_eq!(classification.class("8"), None);
+    }
+
+    #[test]
+    fn classification_invalid() {
+        assert!(Classification::parse("").is_err());
+        assert!(Classification::parse("PSB 1\n").is_err());
+        assert!(Classification::parse("PSB 1\n1 2\nairplane 0 2\n12\n").is_err());
+        assert!(Classification::parse("PSB 1\n1 1\nairplane 0 x\n12\n").is_err());
+    }
+
+    #[test]
+    fn category() {
+        let root = Path::new("ModelNet40");
+        let path = Path::new("ModelNet40/airplane/train/airplane_0001.off");
+
+        assert_eq!(
+            Layout::Directory.category(root, path).as_deref(),
+            Some("airplane")
+        );
+        assert_eq!(
+            Layout::ParentDirectory.category(root, path).as_deref(),
+            Some("train")
+        );
+        assert_eq!(Layout::None.category(root, path), None);
+        assert_eq!(
+            Layout::Directory.category(root, Path::new("ModelNet40/a.off")),
+            None
+        );
+
+        let layout = Layout::Classification(Classification::parse(CLA).unwrap());
+        assert_eq!(
+            layout
+                .category(Path::new("db"), Path::new("db/0/m12/m12.off"))
+                .as_deref(),
+            Some("airplane")
+        );
+        assert_eq!(
+            layout
+                .category(Path::new("db"), Path::new("db/0/m7/m7.off.gz"))
+                .as_deref(),
+            Some("chair")
+        );
+    }
+
+    #[test]
+    fn split_and_model_name() {
+        let root = Path::new("ModelNet10");
+        assert_eq!(
+            split(root, Path::new("ModelNet10/bed/train/bed_0001.off")),
+            Some(Split::Train)
+        );
+        assert_eq!(
+            split(root, Path::new("ModelNet10/bed/test/bed_0001.off")),
+            Some(Split::Test)
+        );
+        assert_eq!(split(root, Path::new("ModelNet10/test.off")), None);
+
+        assert_eq!(model_name(Path::new("a/m1.off")), Some("m1"));
+        assert_eq!(model_name(Path::new("a/m1.OFF.zst")), Some("m1"));
+        assert_eq!(model_name(Path::new("a/m1.stl")), None);
+        assert_eq!(model_name(Path::new("a/m1.gz")), None);
+    }
+}
@@ -0,0 +1,248 @@
+use super::{
+    color::Color,
+    mesh::{Face, FaceIndices, Mesh, Vertex},
+    position::Position,
+    validation::{self, Error, Shape},
+};
+
+/// Builds a [`Mesh`] from vertices and faces added one at a time.
+///
+/// [`MeshBuilder::build`] checks that every face has at least three vertices, like the parser does, and that it only
+/// references existing vertices. Faces may reference vertices that are added after them.
+///
+/// # Examples
+///
+/// ```rust
+/// use off_rs::geometry::{builder::MeshBuilder, position::Position};
+///
+/// let mut builder = MeshBuilder::new();
+/// let a = builder.add_vertex(Position::new(0.0, 0.0, 0.0));
+/// let b = builder.add_vertex(Position::new(1.0, 0.0, 0.0));
+/// let c = builder.add_vertex(Position::new(0.0, 1.0, 0.0));
+/// builder.add_face([a, b, c]);
+///
+/// let mesh = builder.build().unwrap();
+/// assert_eq!(mesh.face_count(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MeshBuilder {
+    vertices: Vec<Vertex>,
+    faces: Vec<Face>,
+    indices: Vec<usize>,
+}
+
+impl MeshBuilder {
+    /// Creates a new, empty [`MeshBuilder`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new, empty [`MeshBuilder`] with capacity for the given number of vertices and faces.
+    #[must_use]
+    pub fn with_capacity(vertex_count: usize, face_count: usize) -> Self {
+        Self {
+            vertices: Vec::with_capacity(vertex_count),
+            faces: Vec::with_capacity(face_count),
+            indices: Vec::with_capacity(face_count.saturating_mul(3)),
+        }
+    }
+
+    /// Returns the number of vertices added so far.
+    #[must_use]
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Returns the number of faces added so far.
+    #[must_use]
+    pub fn face_count(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Adds a vertex without a color and returns its index.
+    pub fn add_vertex(&mut self, position: Position) -> usize {
+        self.push_vertex(Vertex::new(position, None))
+    }
+
+    /// Adds a vertex with the given color and returns its index.
+    pub fn add_colored_vertex(&mut self, position: Position, color: Color) -> usize {
+        self.push_vertex(Vertex::new(position, Some(color)))
+    }
+
+    /// Adds a face without a color and returns its index.
+    ///
+    /// The face is validated by [`MeshBuilder::build`].
+    pub fn add_face<I: IntoIterator<Item = usize>>(&mut self, vertices: I) -> usize {
+        self.push_face(vertices, None)
+    }
+
+    /// Adds a face with the given color and returns its index.
+    ///
+    /// The face is validated by [`MeshBuilder::build`].
+    pub fn add_colored_face<I: IntoIterator<Item = usize>>(
+        &mut self,
+        vertices: I,
+        color: Color,
+    ) -> usize {
+        self.push_face(vertices, Some(color))
+    }
+
+    /// Builds the [`Mesh`], whose faces share a single index buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`Error`] of the first invalid face, if a face has less than three vertices or references a vertex
+    /// that does not exist.
+    pub fn build(self) -> Result<Mesh, Error> {
+        let vertex_count = self.vertices.len();
+
+        for (index, face) in self.faces.iter().enumerate() {
+            let vertices = &self.indices[face.vertices.range()];
+            validation::check_face(index, vertices, vertex_count, Shape::Polygon)?;
+        }
+
+        Ok(self.build_unchecked())
+    }
+
+    /// Builds the [`Mesh`] without validating the faces, for generators that only produce valid faces.
+    pub(crate) fn build_unchecked(mut self) -> Mesh {
+        FaceIndices::attach(
+            self.faces.iter_mut().map(|face| &mut face.vertices),
+            self.indices,
+        );
+
+        Mesh {
+            vertices: self.vertices,
+            faces: self.faces,
+        }
+    }
+
+    /// Appends a vertex and returns its index.
+    pub(crate) fn push_vertex(&mut self, vertex: Vertex) -> usize {
+        self.vertices.push(vertex);
+        self.vertices.len() - 1
+    }
+
+    /// Appends the vertices in order.
+    pub(crate) fn extend_vertices<I: IntoIterator<Item = Vertex>>(&mut self, vertices: I) {
+        self.vertices.extend(vertices);
+    }
+
+    /// Adds a face with the given vertices and the color and colormap index of `face` and returns its index.
+    pub(crate) fn add_face_like<I: IntoIterator<Item = usize>>(
+        &mut self,
+        vertices: I,
+        face: &Face,
+    ) -> usize {
+        let index = self.push_face(vertices, face.color);
+        self.faces[index].color_index = face.color_index;
+        index
+    }
+
+    /// Appends a face whose indices are stored in the shared buffer and returns its index.
+    pub(crate) fn push_face<I: IntoIterator<Item = usize>>(
+        &mut self,
+        vertices: I,
+        color: Option<Color>,
+    ) -> usize {
+        let start = self.indices.len();
+        self.indices.extend(vertices);
+
+        self.faces.push(Face {
+            vertices: FaceIndices::pending(start, self.indices.len()),
+            color,
+            color_index: None,
+        });
+        self.faces.len() - 1
+    }
+}
+
+impl Mesh {
+    /// Creates a new [`MeshBuilder`].
+    #[must_use]
+    pub fn builder() -> MeshBuilder {
+        MeshBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> MeshBuilder {
+        let mut builder = MeshBuilder::new();
+        builder.add_vertex(Position::new(0.0, 0.0, 0.0));
+        builder.add_vertex(Position::new(1.0, 0.0, 0.0));
+        builder.add_vertex(Position::new(0.0, 1.0, 0.0));
+        builder
+    }
+
+    #[test]
+    fn build() {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0).unwrap();
+
+        let mut builder = triangle();
+        assert_eq!(builder.add_face([0, 1, 2]), 0);
+        assert_eq!(builder.add_colored_face(vec![2, 1, 0], red), 1);
+        assert_eq!(
+            builder.add_colored_vertex(Position::new(0.0, 0.0, 1.0), red),
+            3
+        );
+        assert_eq!(builder.vertex_count(), 4);
+        assert_eq!(builder.face_count(), 2);
+
+        let mesh = builder.build().unwrap();
+        assert_eq!(mesh.vertices[3].color, Some(red));
+        assert_eq!(
+            mesh.faces,
+            vec![
+                Face::new(vec![0, 1, 2], None),
+                Face::new(vec![2, 1, 0], Some(red))
+            ]
+        );
+    }
+
+    #[test]
+    fn forward_references() {
+        let mut builder = MeshBuilder::new();
+        builder.add_face(0..3);
+        for _ in 0..3 {
+            builder.add_vertex(Position::default());
+        }
+
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn not_enough_vertices() {
+        let mut builder = triangle();
+        builder.add_face([0, 1, 2]);
+        builder.add_face([0, 1]);
+
+        assert_eq!(
+            builder.build().unwrap_err(),
+            Error::NotEnoughVertices { face: 1, actual: 2 }
+        );
+    }
+
+    #[test]
+    fn vertex_out_of_range() {
+        let mut builder = triangle();
+        builder.add_face([0, 3, 2]);
+
+        assert_eq!(
+            builder.build().unwrap_err(),
+            Error::VertexOutOfRange {
+                face: 0,
+                vertex: 3,
+                vertex_count: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Mesh::builder().build().unwrap(), Mesh::default());
+    }
+}
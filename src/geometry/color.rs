@@ -11,9 +11,9 @@ pub enum Error {
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::FromF32(msg) => write!(f, "Failed to convert `f32` to `Color`: {}", msg),
-            Self::FromU8(msg) => write!(f, "Failed to convert `u8` to `Color`: {}", msg),
-            Self::ToU8(msg) => write!(f, "Failed to convert `Color` to `Vec<u8>`: {}", msg),
+            Self::FromF32(msg) => write!(f, "Failed to convert `f32` to `Color`: {msg}"),
+            Self::FromU8(msg) => write!(f, "Failed to convert `u8` to `Color`: {msg}"),
+            Self::ToU8(msg) => write!(f, "Failed to convert `Color` to `Vec<u8>`: {msg}"),
         }
     }
 }
@@ -42,8 +42,7 @@ impl Color {
             || !(0.0..=1.0).contains(&alpha)
         {
             Err(Error::FromF32(format!(
-                "Color values must be between 0.0 and 1.0, got: ({}, {}, {}, {})",
-                red, green, blue, alpha
+                "Color values must be between 0.0 and 1.0, got: ({red}, {green}, {blue}, {alpha})"
             )))
         } else {
             Ok(Self {
@@ -91,8 +90,7 @@ impl TryFrom<Color> for Vec<u8> {
             || !(0.0..=1.0).contains(&value.alpha)
         {
             return Err(Error::ToU8(format!(
-                "Color values must be between 0.0 and 1.0, got: {:?}",
-                value
+                "Color values must be between 0.0 and 1.0, got: {value:?}"
             )));
         }
 
@@ -105,15 +103,15 @@ impl TryFrom<Color> for Vec<u8> {
     }
 }
 
-impl TryFrom<Vec<f32>> for Color {
+impl TryFrom<&[f32]> for Color {
     type Error = Error;
 
-    /// Converts a [`Vec<f32>`] to a [`Color`]
+    /// Converts a slice of [`f32`] to a [`Color`]
     ///
     /// # Errors
     ///
     /// Returns [`Error::FromF32`] if `value` contains less than three or more than four elements.
-    fn try_from(value: Vec<f32>) -> std::result::Result<Self, Self::Error> {
+    fn try_from(value: &[f32]) -> std::result::Result<Self, Self::Error> {
         if 3 > value.len() || 4 < value.len() {
             return Err(Self::Error::FromF32(format!(
                 "Invalid amount of arguments (expected: 3-4, actual: {})",
@@ -127,15 +125,28 @@ impl TryFrom<Vec<f32>> for Color {
     }
 }
 
-impl TryFrom<Vec<u8>> for Color {
+impl TryFrom<Vec<f32>> for Color {
     type Error = Error;
 
-    /// Converts a [`Vec<u8>`] to a [`Color`]
+    /// Converts a [`Vec<f32>`] to a [`Color`]
     ///
     /// # Errors
     ///
-    /// Returns [`Error::FromU8`] if `value` contains less than four or more than four elements or if the elements are not in the range of 0 to 255.
-    fn try_from(value: Vec<u8>) -> std::result::Result<Self, Self::Error> {
+    /// Returns [`Error::FromF32`] if `value` contains less than three or more than four elements.
+    fn try_from(value: Vec<f32>) -> std::result::Result<Self, Self::Error> {
+        Color::try_from(value.as_slice())
+    }
+}
+
+impl TryFrom<&[u8]> for Color {
+    type Error = Error;
+
+    /// Converts a slice of [`u8`] to a [`Color`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FromU8`] if `value` contains less than three or more than four elements.
+    fn try_from(value: &[u8]) -> std::result::Result<Self, Self::Error> {
         if 3 > value.len() || 4 < value.len() {
             return Err(Self::Error::FromU8(format!(
                 "Invalid amount of arguments (expected: 3-4, actual: {})",
@@ -144,28 +155,29 @@ impl TryFrom<Vec<u8>> for Color {
         }
 
         let alpha = if value.len() == 4 { value[3] } else { 255 };
-        let val = [value[0], value[1], value[2], alpha];
-
-        if !(0..=255).contains(&val[0])
-            || !(0..=255).contains(&val[1])
-            || !(0..=255).contains(&val[2])
-            || !(0..=255).contains(&val[3])
-        {
-            return Err(Error::FromU8(format!(
-                "Color values must be between 0 and 255, got: {:?}",
-                val
-            )));
-        }
 
         Color::new(
-            f32::from(val[0]) / 255.0,
-            f32::from(val[1]) / 255.0,
-            f32::from(val[2]) / 255.0,
-            f32::from(val[3]) / 255.0,
+            f32::from(value[0]) / 255.0,
+            f32::from(value[1]) / 255.0,
+            f32::from(value[2]) / 255.0,
+            f32::from(alpha) / 255.0,
         )
     }
 }
 
+impl TryFrom<Vec<u8>> for Color {
+    type Error = Error;
+
+    /// Converts a [`Vec<u8>`] to a [`Color`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FromU8`] if `value` contains less than three or more than four elements.
+    fn try_from(value: Vec<u8>) -> std::result::Result<Self, Self::Error> {
+        Color::try_from(value.as_slice())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,138 @@
+use super::color::Color;
+
+/// The number of entries of the built-in default [`Colormap`].
+const DEFAULT_LEN: usize = 256;
+
+/// The number of levels per channel of the color cube in the built-in default [`Colormap`].
+const CUBE_LEVELS: usize = 6;
+
+/// A list of colors that faces can reference by index instead of specifying their color values.
+///
+/// The [`Default`](`Colormap::default`) colormap has 256 entries: a 6x6x6 color cube (red varying slowest)
+/// followed by a ramp of 40 grays from black to white.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Colormap {
+    /// The colors of the colormap.
+    pub colors: Vec<Color>,
+}
+
+impl Colormap {
+    /// Creates a new [`Colormap`] from the given colors.
+    #[must_use]
+    pub fn new(colors: Vec<Color>) -> Self {
+        Self { colors }
+    }
+
+    /// Returns the color at `index` or `None` if the index is out of range.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<Color> {
+        self.colors.get(index).copied()
+    }
+
+    /// Returns the number of colors in the colormap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// Returns whether the colormap contains no colors.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// Returns the color at `index` of the built-in default colormap without allocating it.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn default_color(index: usize) -> Option<Color> {
+        let cube_len = CUBE_LEVELS.pow(3);
+
+        if index < cube_len {
+            let level = |value: usize| (value % CUBE_LEVELS) as f32 / (CUBE_LEVELS - 1) as f32;
+
+            Some(Color {
+                red: level(index / (CUBE_LEVELS * CUBE_LEVELS)),
+                green: level(index / CUBE_LEVELS),
+                blue: level(index),
+                alpha: 1.0,
+            })
+        } else if index < DEFAULT_LEN {
+            let gray = (index - cube_len) as f32 / (DEFAULT_LEN - cube_len - 1) as f32;
+
+            Some(Color {
+                red: gray,
+                green: gray,
+                blue: gray,
+                alpha: 1.0,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for Colormap {
+    /// Returns the built-in default colormap.
+    fn default() -> Self {
+        (0..DEFAULT_LEN)
+            .filter_map(Colormap::default_color)
+            .collect()
+    }
+}
+
+impl From<Vec<Color>> for Colormap {
+    fn from(colors: Vec<Color>) -> Self {
+        Self::new(colors)
+    }
+}
+
+impl FromIterator<Color> for Colormap {
+    fn from_iter<I: IntoIterator<Item = Color>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_colormap() {
+        let colormap = Colormap::default();
+        assert_eq!(colormap.len(), 256);
+        assert_eq!(
+            colormap.get(0),
+            Some(Color::new(0.0, 0.0, 0.0, 1.0).unwrap())
+        );
+        assert_eq!(
+            colormap.get(5),
+            Some(Color::new(0.0, 0.0, 1.0, 1.0).unwrap())
+        );
+        assert_eq!(
+            colormap.get(180),
+            Some(Color::new(1.0, 0.0, 0.0, 1.0).unwrap())
+        );
+        assert_eq!(
+            colormap.get(215),
+            Some(Color::new(1.0, 1.0, 1.0, 1.0).unwrap())
+        );
+        assert_eq!(
+            colormap.get(216),
+            Some(Color::new(0.0, 0.0, 0.0, 1.0).unwrap())
+        );
+        assert_eq!(
+            colormap.get(255),
+            Some(Color::new(1.0, 1.0, 1.0, 1.0).unwrap())
+        );
+        assert_eq!(colormap.get(256), None);
+    }
+
+    #[test]
+    fn custom_colormap() {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0).unwrap();
+        let colormap = Colormap::from(vec![red]);
+        assert_eq!(colormap.get(0), Some(red));
+        assert_eq!(colormap.get(1), None);
+        assert!(!colormap.is_empty());
+    }
+}
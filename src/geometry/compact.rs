@@ -0,0 +1,454 @@
+use std::fmt::{Debug, Display, Formatter};
+
+use super::{
+    color::Color,
+    mesh::{Face, FaceIndices, Mesh, Vertex},
+    position::Position,
+};
+
+/// Contains errors that occur while converting a [`Mesh`] to a [`CompactMesh`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Error {
+    FromMesh(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FromMesh(msg) => write!(f, "Failed to convert `Mesh` to `CompactMesh`: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A per-element attribute of a [`CompactMesh`], which may be missing for some elements.
+///
+/// The values are stored in a single array. Only if some elements have no value, a second array marks which values
+/// are present.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Attribute<T> {
+    values: Vec<T>,
+    present: Option<Vec<bool>>,
+}
+
+impl<T: Copy + Default> Attribute<T> {
+    /// Creates an [`Attribute`] where every element has a value.
+    #[must_use]
+    pub fn new(values: Vec<T>) -> Self {
+        Self {
+            values,
+            present: None,
+        }
+    }
+
+    /// Returns the number of elements.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the attribute contains no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns `true` if every element has a value.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.present.is_none()
+    }
+
+    /// Returns the value of the element at `index`, `None` if it has no value or does not exist.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<T> {
+        match &self.present {
+            Some(present) if !present.get(index).copied()? => None,
+            _ => self.values.get(index).copied(),
+        }
+    }
+
+    /// Returns the values of all elements, elements without a value contain `T::default()`.
+    #[must_use]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns an iterator over the values of all elements.
+    pub fn iter(&self) -> impl Iterator<Item = Option<T>> + '_ {
+        (0..self.len()).map(|index| self.get(index))
+    }
+
+    /// Appends the value of the next element.
+    fn push(&mut self, value: Option<T>) {
+        if value.is_none() && self.present.is_none() {
+            self.present = Some(vec![true; self.values.len()]);
+        }
+
+        if let Some(present) = &mut self.present {
+            present.push(value.is_some());
+        }
+
+        self.values.push(value.unwrap_or_default());
+    }
+
+    /// Appends the value of the next element to an optional attribute of `len` elements, which is created once the
+    /// first element has a value.
+    fn push_optional(attribute: &mut Option<Self>, len: usize, value: Option<T>) {
+        match attribute {
+            Some(attribute) => attribute.push(value),
+            None if value.is_some() => {
+                let mut created = Self::default();
+                for _ in 0..len {
+                    created.push(None);
+                }
+                created.push(value);
+                *attribute = Some(created);
+            }
+            None => {}
+        }
+    }
+}
+
+impl<T: Copy + Default> FromIterator<Option<T>> for Attribute<T> {
+    fn from_iter<I: IntoIterator<Item = Option<T>>>(iter: I) -> Self {
+        let mut attribute = Self::default();
+        for value in iter {
+            attribute.push(value);
+        }
+        attribute
+    }
+}
+
+/// A mesh stored as a struct of arrays with [`u32`] indices.
+///
+/// Unlike [`Mesh`] there is no per-vertex padding for missing colors and indices take four instead of eight bytes.
+/// Color arrays are only allocated if at least one element has a color. Use [`Mesh::from`] and
+/// [`CompactMesh::try_from`] to convert between both representations without losing information.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CompactMesh {
+    positions: Vec<Position>,
+    vertex_colors: Option<Attribute<Color>>,
+    indices: Vec<u32>,
+    face_offsets: Vec<u32>,
+    face_colors: Option<Attribute<Color>>,
+    face_color_indices: Option<Attribute<usize>>,
+}
+
+impl Default for CompactMesh {
+    fn default() -> Self {
+        Self {
+            positions: Vec::new(),
+            vertex_colors: None,
+            indices: Vec::new(),
+            face_offsets: vec![0],
+            face_colors: None,
+            face_color_indices: None,
+        }
+    }
+}
+
+impl CompactMesh {
+    /// Creates a new, empty [`CompactMesh`].
+    #[must_use]
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of vertices in the mesh.
+    #[must_use]
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns the number of faces in the mesh.
+    #[must_use]
+    pub fn face_count(&self) -> usize {
+        self.face_offsets.len() - 1
+    }
+
+    /// Returns the positions of all vertices.
+    #[must_use]
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// Returns the colors of the vertices, `None` if no vertex has a color.
+    #[must_use]
+    pub fn vertex_colors(&self) -> Option<&Attribute<Color>> {
+        self.vertex_colors.as_ref()
+    }
+
+    /// Returns the vertex indices of all faces in a single array.
+    #[must_use]
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Returns the offsets of the faces into [`CompactMesh::indices`].
+    ///
+    /// The indices of the face `i` are `indices[offsets[i]..offsets[i + 1]]`, so there is one more offset than faces.
+    #[must_use]
+    pub fn face_offsets(&self) -> &[u32] {
+        &self.face_offsets
+    }
+
+    /// Returns the vertex indices of the face at `index`.
+    #[must_use]
+    pub fn face(&self, index: usize) -> Option<&[u32]> {
+        let start = *self.face_offsets.get(index)? as usize;
+        let end = *self.face_offsets.get(index + 1)? as usize;
+
+        self.indices.get(start..end)
+    }
+
+    /// Returns an iterator over the vertex indices of all faces.
+    pub fn faces(&self) -> impl Iterator<Item = &[u32]> + '_ {
+        self.face_offsets
+            .windows(2)
+            .map(|offsets| &self.indices[offsets[0] as usize..offsets[1] as usize])
+    }
+
+    /// Returns the colors of the faces, `None` if no face has a color.
+    #[must_use]
+    pub fn face_colors(&self) -> Option<&Attribute<Color>> {
+        self.face_colors.as_ref()
+    }
+
+    /// Returns the colormap indices of the faces, `None` if no face references a colormap entry.
+    #[must_use]
+    pub fn face_color_indices(&self) -> Option<&Attribute<usize>> {
+        self.face_color_indices.as_ref()
+    }
+
+    /// Appends a vertex.
+    pub(crate) fn push_vertex(&mut self, vertex: Vertex) {
+        Attribute::push_optional(&mut self.vertex_colors, self.positions.len(), vertex.color);
+        self.positions.push(vertex.position);
+    }
+
+    /// Appends a face with the given vertex indices.
+    ///
+    /// Returns a description of the problem if an index or the total number of indices does not fit into an [`u32`].
+    pub(crate) fn push_face(
+        &mut self,
+        indices: &[usize],
+        color: Option<Color>,
+        color_index: Option<usize>,
+    ) -> Result<(), String> {
+        let start = self.indices.len();
+
+        for &index in indices {
+            let Ok(index) = u32::try_from(index) else {
+                self.indices.truncate(start);
+                return Err(format!("Vertex index {index} does not fit into `u32`"));
+            };
+            self.indices.push(index);
+        }
+
+        let Ok(offset) = u32::try_from(self.indices.len()) else {
+            self.indices.truncate(start);
+            return Err(format!(
+                "Total number of face indices {} does not fit into `u32`",
+                start + indices.len()
+            ));
+        };
+
+        let face_count = self.face_count();
+        Attribute::push_optional(&mut self.face_colors, face_count, color);
+        Attribute::push_optional(&mut self.face_color_indices, face_count, color_index);
+        self.face_offsets.push(offset);
+
+        Ok(())
+    }
+
+    /// Reserves capacity for at least `vertex_count` more vertices, `face_count` more faces and `index_count` more
+    /// face indices.
+    pub(crate) fn reserve(&mut self, vertex_count: usize, face_count: usize, index_count: usize) {
+        self.positions.reserve_exact(vertex_count);
+        self.face_offsets.reserve_exact(face_count);
+        self.indices.reserve_exact(index_count);
+    }
+}
+
+impl TryFrom<&Mesh> for CompactMesh {
+    type Error = Error;
+
+    /// Converts a [`Mesh`] to a [`CompactMesh`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FromMesh`] if a vertex index or the total number of face indices does not fit into an [`u32`].
+    fn try_from(value: &Mesh) -> Result<Self, Self::Error> {
+        let mut mesh = CompactMesh::new();
+        mesh.reserve(
+            value.vertices.len(),
+            value.faces.len(),
+            value.faces.iter().map(|face| face.vertices.len()).sum(),
+        );
+
+        for &vertex in &value.vertices {
+            mesh.push_vertex(vertex);
+        }
+
+        for face in &value.faces {
+            mesh.push_face(&face.vertices, face.color, face.color_index)
+                .map_err(Error::FromMesh)?;
+        }
+
+        Ok(mesh)
+    }
+}
+
+impl TryFrom<Mesh> for CompactMesh {
+    type Error = Error;
+
+    /// Converts a [`Mesh`] to a [`CompactMesh`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::FromMesh`] if a vertex index or the total number of face indices does not fit into an [`u32`].
+    fn try_from(value: Mesh) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl From<&CompactMesh> for Mesh {
+    /// Converts a [`CompactMesh`] to a [`Mesh`], whose faces share a single index buffer.
+    fn from(value: &CompactMesh) -> Self {
+        let vertices = (0..value.vertex_count())
+            .map(|index| {
+                Vertex::new(
+                    value.positions[index],
+                    value
+                        .vertex_colors
+                        .as_ref()
+                        .and_then(|colors| colors.get(index)),
+                )
+            })
+            .collect();
+
+        let mut faces: Vec<Face> = (0..value.face_count())
+            .map(|index| Face {
+                vertices: FaceIndices::pending(
+                    value.face_offsets[index] as usize,
+                    value.face_offsets[index + 1] as usize,
+                ),
+                color: value
+                    .face_colors
+                    .as_ref()
+                    .and_then(|colors| colors.get(index)),
+                color_index: value
+                    .face_color_indices
+                    .as_ref()
+                    .and_then(|indices| indices.get(index)),
+            })
+            .collect();
+
+        let indices = value.indices.iter().map(|&index| index as usize).collect();
+        FaceIndices::attach(faces.iter_mut().map(|face| &mut face.vertices), indices);
+
+        Mesh { vertices, faces }
+    }
+}
+
+impl From<CompactMesh> for Mesh {
+    /// Converts a [`CompactMesh`] to a [`Mesh`], whose faces share a single index buffer.
+    fn from(value: CompactMesh) -> Self {
+        Self::from(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mesh() -> Mesh {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0).unwrap();
+
+        Mesh {
+            vertices: vec![
+                Vertex::new(Position::new(0.0, 0.0, 0.0), None),
+                Vertex::new(Position::new(1.0, 0.0, 0.0), Some(red)),
+                Vertex::new(Position::new(1.0, 1.0, 0.0), None),
+                Vertex::new(Position::new(0.0, 1.0, 0.0), Some(red)),
+            ],
+            faces: vec![
+                Face::new(vec![0, 1, 2], None),
+                Face {
+                    vertices: vec![0, 2, 3].into(),
+                    color: None,
+                    color_index: Some(1000),
+                },
+                Face::new(vec![3, 2, 1, 0], Some(red)),
+            ],
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mesh = mesh();
+        let compact = CompactMesh::try_from(&mesh).unwrap();
+
+        assert_eq!(compact.vertex_count(), 4);
+        assert_eq!(compact.face_count(), 3);
+        assert_eq!(compact.indices(), &[0, 1, 2, 0, 2, 3, 3, 2, 1, 0]);
+        assert_eq!(compact.face_offsets(), &[0, 3, 6, 10]);
+        assert_eq!(compact.face(2), Some(&[3, 2, 1, 0][..]));
+        assert_eq!(compact.face(3), None);
+        assert_eq!(compact.faces().count(), 3);
+
+        assert_eq!(Mesh::from(&compact), mesh);
+        assert_eq!(Mesh::from(CompactMesh::default()), Mesh::default());
+    }
+
+    #[test]
+    fn colors_only_when_present() {
+        let mut mesh = mesh();
+        for vertex in &mut mesh.vertices {
+            vertex.color = None;
+        }
+        mesh.faces[2].color = None;
+        mesh.faces[1].color_index = None;
+
+        let compact = CompactMesh::try_from(&mesh).unwrap();
+        assert_eq!(compact.vertex_colors(), None);
+        assert_eq!(compact.face_colors(), None);
+        assert_eq!(compact.face_color_indices(), None);
+        assert_eq!(Mesh::from(compact), mesh);
+    }
+
+    #[test]
+    fn attribute() {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0).unwrap();
+
+        let complete: Attribute<Color> = [Some(red), Some(red)].into_iter().collect();
+        assert!(complete.is_complete());
+        assert_eq!(complete, Attribute::new(vec![red, red]));
+
+        let partial: Attribute<Color> = [Some(red), None, Some(red)].into_iter().collect();
+        assert!(!partial.is_complete());
+        assert_eq!(partial.len(), 3);
+        assert_eq!(partial.get(1), None);
+        assert_eq!(partial.get(2), Some(red));
+        assert_eq!(partial.get(3), None);
+        assert_eq!(partial.values()[1], Color::default());
+        assert_eq!(
+            partial.iter().collect::<Vec<_>>(),
+            vec![Some(red), None, Some(red)]
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn index_overflow() {
+        let mut mesh = mesh();
+        mesh.faces[0] = Face::new(vec![0, 1, 1 << 32], None);
+
+        assert!(matches!(
+            CompactMesh::try_from(&mesh),
+            Err(Error::FromMesh(_))
+        ));
+    }
+}
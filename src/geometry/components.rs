@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+
+use super::{mesh::Mesh, submesh::Extractor, validation::Error};
+
+/// Defines when two faces belong to the same connected component.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Hash)]
+pub enum Connectivity {
+    /// Faces are connected if they share at least one vertex.
+    #[default]
+    Vertex,
+    /// Faces are connected if they share an edge, regardless of its direction.
+    Edge,
+}
+
+/// A labeling of the faces and vertices of a [`Mesh`] by connected component, see [`Mesh::connected_components`].
+///
+/// Components are numbered from zero in the order of their first face.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Components {
+    /// The component of every face.
+    pub face_labels: Vec<usize>,
+    /// The component of every vertex, or `None` if no face references the vertex.
+    ///
+    /// With [`Connectivity::Edge`] a vertex can be shared by several components, it is then labeled with the
+    /// component of the first face that references it.
+    pub vertex_labels: Vec<Option<usize>>,
+    count: usize,
+}
+
+impl Components {
+    /// Returns the number of components.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the number of faces of every component.
+    #[must_use]
+    pub fn sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.count];
+        for &label in &self.face_labels {
+            sizes[label] += 1;
+        }
+        sizes
+    }
+
+    /// Returns the indices of the faces of the given component in ascending order.
+    pub fn faces(&self, component: usize) -> impl Iterator<Item = usize> + '_ {
+        self.face_labels
+            .iter()
+            .enumerate()
+            .filter(move |&(_, &label)| label == component)
+            .map(|(face, _)| face)
+    }
+}
+
+impl Mesh {
+    /// Labels the faces and vertices of the mesh by connected component.
+    ///
+    /// Vertex indices that are out of range do not connect faces.
+    #[must_use]
+    pub fn connected_components(&self, connectivity: Connectivity) -> Components {
+        let mut sets = DisjointSets::new(self.faces.len());
+
+        match connectivity {
+            Connectivity::Vertex => {
+                let mut first_faces = vec![None; self.vertices.len()];
+
+                for (face, indices) in self.faces.iter().enumerate() {
+                    for &vertex in &indices.vertices {
+                        if let Some(first) = first_faces.get_mut(vertex) {
+                            sets.union(*first.get_or_insert(face), face);
+                        }
+                    }
+                }
+            }
+            Connectivity::Edge => {
+                let mut first_faces = HashMap::new();
+
+                for (face, indices) in self.faces.iter().enumerate() {
+                    let vertices = &indices.vertices;
+
+                    for (i, &a) in vertices.iter().enumerate() {
+                        let b = vertices[(i + 1) % vertices.len()];
+
+                        if a != b && a < self.vertices.len() && b < self.vertices.len() {
+                            sets.union(
+                                *first_faces.entry((a.min(b), a.max(b))).or_insert(face),
+                                face,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut components = Components {
+            face_labels: Vec::with_capacity(self.faces.len()),
+            vertex_labels: vec![None; self.vertices.len()],
+            count: 0,
+        };
+        let mut labels = HashMap::new();
+
+        for (face, indices) in self.faces.iter().enumerate() {
+            let label = *labels.entry(sets.find(face)).or_insert_with(|| {
+                components.count += 1;
+                components.count - 1
+            });
+            components.face_labels.push(label);
+
+            for &vertex in &indices.vertices {
+                if let Some(vertex_label) = components.vertex_labels.get_mut(vertex) {
+                    vertex_label.get_or_insert(label);
+                }
+            }
+        }
+
+        components
+    }
+
+    /// Splits the mesh into one mesh per connected component, in the order of [`Components`].
+    ///
+    /// Every mesh only contains the vertices referenced by its faces.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a face references a vertex that does not exist.
+    pub fn split_components(&self, connectivity: Connectivity) -> Result<Vec<Mesh>, Error> {
+        let components = self.connected_components(connectivity);
+
+        let mut faces: Vec<Vec<usize>> = components
+            .sizes()
+            .into_iter()
+            .map(Vec::with_capacity)
+            .collect();
+        for (face, &label) in components.face_labels.iter().enumerate() {
+            faces[label].push(face);
+        }
+
+        let mut extractor = Extractor::new(self);
+        faces
+            .into_iter()
+            .map(|faces| extractor.extract(faces))
+            .collect()
+    }
+
+    /// Returns a copy of the mesh with only the `count` components with the most faces.
+    ///
+    /// Components with the same number of faces are kept in the order of [`Components`]. The faces keep their order
+    /// and unreferenced vertices are dropped, which removes floating debris from scanned meshes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a face references a vertex that does not exist.
+    pub fn keep_largest_components(
+        &self,
+        count: usize,
+        connectivity: Connectivity,
+    ) -> Result<Mesh, Error> {
+        let components = self.connected_components(connectivity);
+        let sizes = components.sizes();
+
+        let mut order: Vec<usize> = (0..components.count()).collect();
+        order.sort_by_key(|&component| std::cmp::Reverse(sizes[component]));
+
+        let mut keep = vec![false; components.count()];
+        for &component in order.iter().take(count) {
+            keep[component] = true;
+        }
+
+        let faces = components
+            .face_labels
+            .iter()
+            .enumerate()
+            .filter(|&(_, &label)| keep[label])
+            .map(|(face, _)| face);
+
+        Ok(self.submesh(faces)?.mesh)
+    }
+}
+
+/// A union-find structure over the indices `0..len`.
+struct DisjointSets {
+    parents: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    /// Returns the representative of the set containing `index`.
+    fn find(&mut self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            // Path halving keeps the trees flat
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+
+    /// Merges the sets containing `a` and `b`.
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        self.parents[a.max(b)] = a.min(b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        geometry::{
+            mesh::{Face, Vertex},
+            position::Position,
+        },
+        primitives,
+    };
+
+    /// Two triangles touching at vertex 2, followed by a separate triangle and an unreferenced vertex.
+    fn bowtie() -> Mesh {
+        Mesh {
+            vertices: vec![Vertex::default(); 9],
+            faces: vec![
+                Face::new(vec![0, 1, 2], None),
+                Face::new(vec![5, 6, 7], None),
+                Face::new(vec![2, 3, 4], None),
+            ],
+        }
+    }
+
+    #[test]
+    fn vertex_connectivity() {
+        let components = bowtie().connected_components(Connectivity::Vertex);
+        assert_eq!(components.count(), 2);
+        assert_eq!(components.face_labels, vec![0, 1, 0]);
+        assert_eq!(
+            components.vertex_labels,
+            vec![
+                Some(0),
+                Some(0),
+                Some(0),
+                Some(0),
+                Some(0),
+                Some(1),
+                Some(1),
+                Some(1),
+                None
+            ]
+        );
+        assert_eq!(components.sizes(), vec![2, 1]);
+        assert_eq!(components.faces(0).collect::<Vec<_>>(), vec![0, 2]);
+    }
+
+    #[test]
+    fn edge_connectivity() {
+        let components = bowtie().connected_components(Connectivity::Edge);
+        assert_eq!(components.count(), 3);
+        assert_eq!(components.face_labels, vec![0, 1, 2]);
+        assert_eq!(components.vertex_labels[2], Some(0));
+        assert_eq!(components.vertex_labels[3], Some(2));
+
+        // Every face of a closed mesh shares edges with its neighbours
+        let sphere = primitives::uv_sphere(1.0, 8, 4);
+        assert_eq!(sphere.connected_components(Connectivity::Edge).count(), 1);
+    }
+
+    #[test]
+    fn split_components() {
+        let first = primitives::cube(1.0);
+        let second = primitives::icosphere(1.0, 1);
+        let merged = Mesh::merge([&first, &second, &first]);
+
+        let parts = merged.split_components(Connectivity::Edge).unwrap();
+        assert_eq!(parts, vec![first.clone(), second, first]);
+    }
+
+    #[test]
+    fn split_shared_vertex() {
+        // With edge connectivity the first and last face share vertex 2 but belong to different components
+        let parts = bowtie().split_components(Connectivity::Edge).unwrap();
+        assert_eq!(parts.len(), 3);
+        for part in &parts {
+            assert_eq!(part.vertex_count(), 3);
+            assert_eq!(part.faces, vec![Face::new(vec![0, 1, 2], None)]);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss, clippy::float_cmp)]
+    fn split_many_components() {
+        let count = 20_000;
+        let mesh = Mesh {
+            vertices: (0..count * 3)
+                .map(|i| Vertex::new(Position::new(i as f32, 0.0, 0.0), None))
+                .collect(),
+            faces: (0..count)
+                .map(|i| Face::new(vec![3 * i, 3 * i + 1, 3 * i + 2], None))
+                .collect(),
+        };
+
+        let parts = mesh.split_components(Connectivity::Vertex).unwrap();
+        assert_eq!(parts.len(), count);
+        assert_eq!(
+            parts[count - 1].vertices[0].position.x,
+            (3 * count - 3) as f32
+        );
+        assert!(parts.iter().all(|part| part.vertex_count() == 3));
+    }
+
+    #[test]
+    fn keep_largest_components() {
+        let small = primitives::cube(1.0);
+        let large = primitives::icosphere(1.0, 0);
+        let merged = Mesh::merge([&small, &large, &small]);
+
+        let largest = merged
+            .keep_largest_components(1, Connectivity::Vertex)
+            .unwrap();
+        assert_eq!(largest, large);
+
+        let two = merged
+            .keep_largest_components(2, Connectivity::Vertex)
+            .unwrap();
+        assert_eq!(two, Mesh::merge([&small, &large]));
+
+        assert_eq!(
+            merged
+                .keep_largest_components(5, Connectivity::Vertex)
+                .unwrap(),
+            merged
+        );
+        assert_eq!(
+            merged
+                .keep_largest_components(0, Connectivity::Vertex)
+                .unwrap(),
+            Mesh::default()
+        );
+    }
+
+    #[test]
+    fn invalid_vertices() {
+        let mut mesh = bowtie();
+        mesh.faces.push(Face::new(vec![8, 2, 9], None));
+
+        let components = mesh.connected_components(Connectivity::Vertex);
+        assert_eq!(components.face_labels, vec![0, 1, 0, 0]);
+        assert_eq!(components.vertex_labels[8], Some(0));
+        assert!(mesh.split_components(Connectivity::Vertex).is_err());
+    }
+}
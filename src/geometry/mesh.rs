@@ -1,4 +1,9 @@
-use super::{color::Color, position::Position};
+use super::{builder::MeshBuilder, color::Color, colormap::Colormap, position::Position};
+use std::{
+    fmt,
+    ops::{Deref, DerefMut, Range},
+    sync::{Arc, OnceLock},
+};
 
 /// Represents a vertex of a mesh.
 /// A vertex contains a position and optionally a vertex color.
@@ -18,28 +23,254 @@ impl Vertex {
     }
 }
 
+/// The vertex indices of a [`Face`].
+///
+/// Dereferences to a `[usize]` slice. The parser stores the indices of all faces in a single flat buffer, which the
+/// faces share as ranges, so a parsed mesh does not need an allocation per face.
+///
+/// Modifying the indices, either through [`FaceIndices::make_mut`] or through the mutable slice, copies them out of a
+/// shared buffer first.
+#[derive(Clone)]
+pub struct FaceIndices {
+    buffer: Arc<Vec<usize>>,
+    start: usize,
+    /// The end of the range in the buffer, or `None` if the indices own the whole buffer.
+    end: Option<usize>,
+}
+
+/// The empty buffer used by default and by pending [`FaceIndices`].
+static EMPTY: OnceLock<Arc<Vec<usize>>> = OnceLock::new();
+
+impl FaceIndices {
+    /// Creates [`FaceIndices`] for the range `start..end` of a buffer which is still being filled.
+    ///
+    /// The indices must be attached to the buffer with [`FaceIndices::attach`] before they are accessed.
+    pub(crate) fn pending(start: usize, end: usize) -> Self {
+        Self {
+            buffer: Arc::clone(EMPTY.get_or_init(Arc::default)),
+            start,
+            end: Some(end),
+        }
+    }
+
+    /// Returns the range of the indices in their buffer, which is also available while they are pending.
+    pub(crate) fn range(&self) -> Range<usize> {
+        self.start..self.end.unwrap_or(self.buffer.len())
+    }
+
+    /// Moves the range of pending [`FaceIndices`] by `offset`, e.g. when buffers are concatenated.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn shift(&mut self, offset: usize) {
+        self.start += offset;
+        self.end = self.end.map(|end| end + offset);
+    }
+
+    /// Attaches pending [`FaceIndices`] to the filled `buffer`, which they share afterwards.
+    pub(crate) fn attach<'f, I>(indices: I, buffer: Vec<usize>)
+    where
+        I: IntoIterator<Item = &'f mut FaceIndices>,
+    {
+        let buffer = Arc::new(buffer);
+
+        for indices in indices {
+            debug_assert!(indices.range().start <= indices.range().end);
+            debug_assert!(indices.range().end <= buffer.len());
+            indices.buffer = Arc::clone(&buffer);
+        }
+    }
+
+    /// Returns the indices as a slice.
+    #[must_use]
+    pub fn as_slice(&self) -> &[usize] {
+        match self.end {
+            Some(end) => &self.buffer[self.start..end],
+            None => &self.buffer[self.start..],
+        }
+    }
+
+    /// Returns the indices as a mutable [`Vec`].
+    ///
+    /// If the indices are a range of a buffer shared with other faces, they are copied into a buffer of their own
+    /// first, which also stops them from keeping the shared buffer alive.
+    pub fn make_mut(&mut self) -> &mut Vec<usize> {
+        if !self.owns_buffer() {
+            self.buffer = Arc::new(self.as_slice().to_vec());
+        }
+        self.start = 0;
+        self.end = None;
+
+        Arc::make_mut(&mut self.buffer)
+    }
+
+    /// Returns `true` if the indices span their whole buffer, which may still be shared with clones.
+    fn owns_buffer(&self) -> bool {
+        self.start == 0 && self.end.is_none_or(|end| end == self.buffer.len())
+    }
+}
+
+impl Default for FaceIndices {
+    fn default() -> Self {
+        Self::pending(0, 0)
+    }
+}
+
+impl Deref for FaceIndices {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for FaceIndices {
+    fn deref_mut(&mut self) -> &mut [usize] {
+        self.make_mut()
+    }
+}
+
+impl AsRef<[usize]> for FaceIndices {
+    fn as_ref(&self) -> &[usize] {
+        self.as_slice()
+    }
+}
+
+impl fmt::Debug for FaceIndices {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl PartialEq for FaceIndices {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for FaceIndices {}
+
+impl PartialEq<[usize]> for FaceIndices {
+    fn eq(&self, other: &[usize]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<const N: usize> PartialEq<[usize; N]> for FaceIndices {
+    fn eq(&self, other: &[usize; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl PartialEq<Vec<usize>> for FaceIndices {
+    fn eq(&self, other: &Vec<usize>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl From<Vec<usize>> for FaceIndices {
+    fn from(value: Vec<usize>) -> Self {
+        Self {
+            buffer: Arc::new(value),
+            start: 0,
+            end: None,
+        }
+    }
+}
+
+impl From<&[usize]> for FaceIndices {
+    fn from(value: &[usize]) -> Self {
+        value.to_vec().into()
+    }
+}
+
+impl<const N: usize> From<[usize; N]> for FaceIndices {
+    fn from(value: [usize; N]) -> Self {
+        value.to_vec().into()
+    }
+}
+
+impl FromIterator<usize> for FaceIndices {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        iter.into_iter().collect::<Vec<_>>().into()
+    }
+}
+
+impl From<FaceIndices> for Vec<usize> {
+    /// Converts [`FaceIndices`] to a [`Vec<usize>`], reusing the buffer if it is not shared.
+    fn from(value: FaceIndices) -> Vec<usize> {
+        if value.owns_buffer() {
+            Arc::try_unwrap(value.buffer).unwrap_or_else(|buffer| buffer.to_vec())
+        } else {
+            value.as_slice().to_vec()
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a FaceIndices {
+    type Item = &'a usize;
+    type IntoIter = std::slice::Iter<'a, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
 /// Represents a face of a mesh.
 /// A face contains a list of vertex indicies and optionally a color.
 #[derive(Clone, PartialEq, Debug, Default)]
 pub struct Face {
     /// The list of vertex indicies that make up the face.
-    pub vertices: Vec<usize>,
+    pub vertices: FaceIndices,
     /// The color of the face.
     pub color: Option<Color>,
+    /// The index into a [`Colormap`] if the color of the face was given as a single colormap index.
+    pub color_index: Option<usize>,
 }
 
 impl Face {
     /// Creates a new [`Face`].
     #[must_use]
-    pub fn new(vertices: Vec<usize>, color: Option<Color>) -> Self {
-        Self { vertices, color }
+    pub fn new<V: Into<FaceIndices>>(vertices: V, color: Option<Color>) -> Self {
+        Self {
+            vertices: vertices.into(),
+            color,
+            color_index: None,
+        }
+    }
+
+    /// Creates a new [`Face`] whose color is the entry at `color_index` of the `colormap`.
+    ///
+    /// The color is `None` if the index is out of range of the colormap, but the index is kept.
+    #[must_use]
+    pub fn with_color_index<V: Into<FaceIndices>>(
+        vertices: V,
+        color_index: usize,
+        colormap: &Colormap,
+    ) -> Self {
+        Self {
+            vertices: vertices.into(),
+            color: colormap.get(color_index),
+            color_index: Some(color_index),
+        }
+    }
+
+    /// Returns the triangles of a fan triangulation of the face.
+    ///
+    /// Faces with less than three vertices yield no triangles.
+    pub fn triangles(&self) -> impl Iterator<Item = [usize; 3]> + '_ {
+        let first = self.vertices.first().copied().unwrap_or_default();
+
+        self.vertices
+            .get(1..)
+            .unwrap_or_default()
+            .windows(2)
+            .map(move |pair| [first, pair[0], pair[1]])
     }
 }
 
 impl From<Face> for Vec<usize> {
     /// Converts a [`Face`] to a [`Vec<usize>`] containing the vertex indicies.
     fn from(value: Face) -> Vec<usize> {
-        value.vertices
+        value.vertices.into()
     }
 }
 
@@ -77,4 +308,247 @@ impl Mesh {
     pub fn edge_count(&self) -> usize {
         self.faces.iter().map(|face| face.vertices.len() - 1).sum()
     }
+
+    /// Calculates the axis-aligned bounding box of the mesh as its minimum and maximum corner.
+    ///
+    /// Returns `None` if the mesh has no vertices.
+    #[must_use]
+    pub fn bounding_box(&self) -> Option<(Position, Position)> {
+        let first = self.vertices.first()?.position;
+
+        Some(
+            self.vertices
+                .iter()
+                .fold((first, first), |(min, max), vertex| {
+                    (min.min(vertex.position), max.max(vertex.position))
+                }),
+        )
+    }
+
+    /// Calculates the normal of the face with the given index using Newell's method.
+    ///
+    /// Returns the zero vector for degenerate faces and `None` if the face does not exist
+    /// or references a vertex that does not exist.
+    #[must_use]
+    pub fn face_normal(&self, face_index: usize) -> Option<Position> {
+        let face = self.faces.get(face_index)?;
+        let mut normal = Position::default();
+
+        for (i, &current) in face.vertices.iter().enumerate() {
+            let next = face.vertices[(i + 1) % face.vertices.len()];
+            let current = self.vertices.get(current)?.position;
+            let next = self.vertices.get(next)?.position;
+
+            normal.x += (current.y - next.y) * (current.z + next.z);
+            normal.y += (current.z - next.z) * (current.x + next.x);
+            normal.z += (current.x - next.x) * (current.y + next.y);
+        }
+
+        Some(normal.normalized())
+    }
+
+    /// Resolves the colors of all faces which reference a colormap index using the given [`Colormap`].
+    ///
+    /// Faces whose index is out of range of the colormap have no color afterwards.
+    pub fn apply_colormap(&mut self, colormap: &Colormap) {
+        for face in &mut self.faces {
+            if let Some(color_index) = face.color_index {
+                face.color = colormap.get(color_index);
+            }
+        }
+    }
+
+    /// Returns a copy of the mesh where every face is split into triangles.
+    ///
+    /// Faces are fan triangulated and every resulting triangle keeps the color of its face.
+    #[must_use]
+    pub fn triangulate(&self) -> Self {
+        let faces = self
+            .faces
+            .iter()
+            .flat_map(|face| {
+                face.triangles().map(move |triangle| Face {
+                    vertices: triangle.into(),
+                    ..face.clone()
+                })
+            })
+            .collect();
+
+        Self {
+            vertices: self.vertices.clone(),
+            faces,
+        }
+    }
+
+    /// Combines the given meshes into one.
+    ///
+    /// The vertices and faces are appended in order and the vertex indices of every face are offset by the number of
+    /// vertices of the preceding meshes. The faces of the merged mesh share a single index buffer.
+    #[must_use]
+    pub fn merge<'m, I: IntoIterator<Item = &'m Mesh>>(meshes: I) -> Self {
+        let mut builder = MeshBuilder::new();
+
+        for mesh in meshes {
+            let offset = builder.vertex_count();
+            builder.extend_vertices(mesh.vertices.iter().copied());
+
+            for face in &mesh.faces {
+                builder.add_face_like(face.vertices.iter().map(|&index| index + offset), face);
+            }
+        }
+
+        builder.build_unchecked()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quad() -> Mesh {
+        Mesh {
+            vertices: vec![
+                Vertex::new(Position::new(0.0, 0.0, 0.0), None),
+                Vertex::new(Position::new(1.0, 0.0, 0.0), None),
+                Vertex::new(Position::new(1.0, 1.0, 0.0), None),
+                Vertex::new(Position::new(0.0, 1.0, 0.0), None),
+            ],
+            faces: vec![Face::new(vec![0, 1, 2, 3], None)],
+        }
+    }
+
+    #[test]
+    fn triangles() {
+        assert_eq!(
+            Face::new(vec![0, 1, 2, 3], None)
+                .triangles()
+                .collect::<Vec<_>>(),
+            vec![[0, 1, 2], [0, 2, 3]]
+        );
+        assert_eq!(Face::new(vec![0, 1], None).triangles().count(), 0);
+        assert_eq!(Face::default().triangles().count(), 0);
+    }
+
+    #[test]
+    fn face_indices() {
+        let mut faces = [FaceIndices::pending(0, 3), FaceIndices::pending(2, 6)];
+        assert_eq!(faces[1].range().len(), 4);
+        FaceIndices::attach(&mut faces, vec![0, 1, 2, 3, 0, 4]);
+
+        assert_eq!(faces[0], [0, 1, 2]);
+        assert_eq!(faces[1], vec![2, 3, 0, 4]);
+        assert_eq!(faces[1].len(), 4);
+        assert_eq!(format!("{:?}", faces[0]), "[0, 1, 2]");
+        assert_eq!(faces[0], FaceIndices::from(vec![0, 1, 2]));
+        assert_ne!(faces[0], faces[1]);
+
+        // The faces share the buffer
+        assert_eq!(faces[0][2..].as_ptr(), faces[1].as_ptr());
+
+        assert_eq!(Vec::from(faces[1].clone()), vec![2, 3, 0, 4]);
+        assert_eq!(Vec::from(FaceIndices::from(vec![5, 6])), vec![5, 6]);
+        assert!(FaceIndices::default().is_empty());
+    }
+
+    #[test]
+    fn face_indices_make_mut() {
+        let mut faces = [FaceIndices::pending(0, 3), FaceIndices::pending(2, 6)];
+        FaceIndices::attach(&mut faces, vec![0, 1, 2, 3, 0, 4]);
+        let [first, second] = &mut faces;
+
+        // Modifying one face copies its indices and leaves the other one untouched
+        first.reverse();
+        second[0] = 7;
+        second.make_mut().push(5);
+        assert_eq!(*first, [2, 1, 0]);
+        assert_eq!(*second, [7, 3, 0, 4, 5]);
+
+        // Owned indices are modified in place, clones keep their values
+        let mut owned = FaceIndices::from(vec![9, 2]);
+        let pointer = owned.as_ptr();
+        owned[0] = 1;
+        assert_eq!(owned.as_ptr(), pointer);
+        owned.make_mut().push(3);
+
+        let clone = owned.clone();
+        owned[0] = 0;
+        assert_eq!((owned, clone), (vec![0, 2, 3].into(), vec![1, 2, 3].into()));
+
+        let mut empty = FaceIndices::default();
+        empty.make_mut().push(1);
+        assert_eq!(empty, [1]);
+        assert!(FaceIndices::default().is_empty());
+    }
+
+    #[test]
+    fn bounding_box() {
+        assert_eq!(
+            quad().bounding_box(),
+            Some((Position::new(0.0, 0.0, 0.0), Position::new(1.0, 1.0, 0.0)))
+        );
+        assert_eq!(Mesh::default().bounding_box(), None);
+    }
+
+    #[test]
+    fn face_normal() {
+        let mesh = quad();
+        assert_eq!(mesh.face_normal(0), Some(Position::new(0.0, 0.0, 1.0)));
+        assert_eq!(mesh.face_normal(1), None);
+    }
+
+    #[test]
+    fn apply_colormap() {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0).unwrap();
+        let mut mesh = quad();
+        mesh.faces[0] = Face::with_color_index(vec![0, 1, 2, 3], 1, &Colormap::default());
+        assert_eq!(mesh.faces[0].color, Colormap::default().get(1));
+
+        mesh.apply_colormap(&Colormap::new(vec![red, red]));
+        assert_eq!(mesh.faces[0].color, Some(red));
+        assert_eq!(mesh.faces[0].color_index, Some(1));
+
+        mesh.apply_colormap(&Colormap::new(vec![red]));
+        assert_eq!(mesh.faces[0].color, None);
+    }
+
+    #[test]
+    fn triangulate() {
+        let mesh = quad().triangulate();
+        assert_eq!(mesh.vertex_count(), 4);
+        assert_eq!(
+            mesh.faces,
+            vec![
+                Face::new(vec![0, 1, 2], None),
+                Face::new(vec![0, 2, 3], None)
+            ]
+        );
+    }
+
+    #[test]
+    fn merge() {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0).unwrap();
+        let mut triangles = quad().triangulate();
+        triangles.faces[1].color = Some(red);
+
+        let merged = Mesh::merge([&quad(), &triangles]);
+        assert_eq!(merged.vertex_count(), 8);
+        assert_eq!(merged.vertices[4..], triangles.vertices[..]);
+        assert_eq!(
+            merged.faces,
+            vec![
+                Face::new(vec![0, 1, 2, 3], None),
+                Face::new(vec![4, 5, 6], None),
+                Face::new(vec![4, 6, 7], Some(red))
+            ]
+        );
+
+        // The faces share the buffer
+        assert_eq!(
+            merged.faces[0].vertices.as_ptr_range().end,
+            merged.faces[1].vertices.as_ptr()
+        );
+
+        assert_eq!(Mesh::merge([]), Mesh::default());
+        assert_eq!(Mesh::merge([&quad()]), quad());
+    }
 }
@@ -1,3 +1,11 @@
+pub mod builder;
 pub mod color;
+pub mod colormap;
+pub mod compact;
+pub mod components;
 pub mod mesh;
 pub mod position;
+pub mod simplify;
+pub mod subdivision;
+pub mod submesh;
+pub mod validation;
@@ -1,4 +1,5 @@
 use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
 
 /// Contains error that occur while performing conversions of the position.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -9,7 +10,7 @@ pub enum Error {
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::FromF32(msg) => write!(f, "Failed to convert `f32` to `Position`: {}", msg),
+            Self::FromF32(msg) => write!(f, "Failed to convert `f32` to `Position`: {msg}"),
         }
     }
 }
@@ -36,6 +37,93 @@ impl Position {
     }
 }
 
+impl Position {
+    /// Returns the dot product of `self` and `other`.
+    #[must_use]
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns the cross product of `self` and `other`.
+    #[must_use]
+    pub fn cross(self, other: Self) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    /// Returns the euclidean length of the position interpreted as a vector.
+    #[must_use]
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    /// Returns the position scaled to unit length, or the zero vector if the length is zero.
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        let length = self.length();
+        if length > 0.0 {
+            self * (1.0 / length)
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Returns the component-wise minimum of `self` and `other`.
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        Self::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// Returns the component-wise maximum of `self` and `other`.
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Self::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+}
+
+impl Add for Position {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Position {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<f32> for Position {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for Position {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
 impl From<Position> for Vec<f32> {
     /// Converts a [`Position`] to a [`Vec`] of three [`f32`]s.
     fn from(value: Position) -> Vec<f32> {
@@ -82,6 +170,21 @@ mod tests {
         assert_eq!(Vec::from(pos), vec![1.0, 2.0, 3.0]);
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn vector_math() {
+        let a = Position::new(1.0, 0.0, 0.0);
+        let b = Position::new(0.0, 1.0, 0.0);
+        assert_eq!(a.cross(b), Position::new(0.0, 0.0, 1.0));
+        assert_eq!(a.dot(b), 0.0);
+        assert_eq!(a + b - a, b);
+        assert_eq!((a * 3.0).length(), 3.0);
+        assert_eq!((a * 3.0).normalized(), a);
+        assert_eq!(Position::default().normalized(), Position::default());
+        assert_eq!(a.min(-b), Position::new(0.0, -1.0, 0.0));
+        assert_eq!(a.max(-b), Position::new(1.0, 0.0, 0.0));
+    }
+
     #[test]
     fn try_from_positiom() {
         let vec = vec![1.0, 2.0, 3.0];
@@ -0,0 +1,697 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    ops::{Add, AddAssign},
+};
+
+use super::{
+    builder::MeshBuilder,
+    color::Color,
+    mesh::{Mesh, Vertex},
+    position::Position,
+    validation::{self, Error, Shape},
+};
+
+/// Defines the options for [`Mesh::simplify_with_options`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SimplifyOptions {
+    /// Whether edges that belong to a single face are kept in place.
+    ///
+    /// Boundary vertices can still be merged along straight boundaries, which does not change the outline.
+    pub preserve_boundaries: bool,
+    /// Whether merging vertices with different colors adds to the cost of a collapse, which keeps color borders
+    /// intact for longer. Vertex colors are interpolated either way.
+    pub preserve_colors: bool,
+}
+
+impl Default for SimplifyOptions {
+    /// Creates a new [`SimplifyOptions`] which preserves boundaries, but not colors.
+    fn default() -> Self {
+        Self {
+            preserve_boundaries: true,
+            preserve_colors: false,
+        }
+    }
+}
+
+/// The weight of the planes that keep boundary edges in place, relative to the planes of the faces.
+const BOUNDARY_WEIGHT: f64 = 1000.0;
+
+impl Mesh {
+    /// Reduces a triangle mesh to at most `target_face_count` faces with the default [`SimplifyOptions`].
+    ///
+    /// See [`Mesh::simplify_with_options`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a face is not a triangle or references a vertex that does not exist.
+    pub fn simplify(&self, target_face_count: usize) -> Result<Mesh, Error> {
+        self.simplify_with_options(target_face_count, SimplifyOptions::default())
+    }
+
+    /// Reduces a triangle mesh to at most `target_face_count` faces by repeatedly collapsing the edge with the lowest
+    /// quadric error, as described by Garland and Heckbert.
+    ///
+    /// Collapses which would fold a face over or make the mesh non-manifold are skipped, so the result can have more
+    /// faces than requested. The remaining faces keep their order and color, unreferenced vertices are dropped. Use
+    /// [`Mesh::triangulate`] to simplify meshes with other polygons.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a face is not a triangle or references a vertex that does not exist.
+    pub fn simplify_with_options(
+        &self,
+        target_face_count: usize,
+        options: SimplifyOptions,
+    ) -> Result<Mesh, Error> {
+        let mut simplifier = Simplifier::new(self, options)?;
+        simplifier.run(target_face_count);
+        Ok(simplifier.finish(self))
+    }
+}
+
+/// A symmetric 4x4 matrix measuring the squared distance of a point to a set of planes.
+#[derive(Copy, Clone, Debug, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    /// Creates the quadric of the plane `ax + by + cz + d = 0` with the given weight.
+    fn plane([a, b, c, d]: [f64; 4], weight: f64) -> Self {
+        Self(
+            [
+                a * a,
+                a * b,
+                a * c,
+                a * d,
+                b * b,
+                b * c,
+                b * d,
+                c * c,
+                c * d,
+                d * d,
+            ]
+            .map(|v| v * weight),
+        )
+    }
+
+    /// Returns the error of the given point.
+    fn error(&self, [x, y, z]: [f64; 3]) -> f64 {
+        let q = &self.0;
+        q[0] * x * x
+            + 2.0 * q[1] * x * y
+            + 2.0 * q[2] * x * z
+            + 2.0 * q[3] * x
+            + q[4] * y * y
+            + 2.0 * q[5] * y * z
+            + 2.0 * q[6] * y
+            + q[7] * z * z
+            + 2.0 * q[8] * z
+            + q[9]
+    }
+
+    /// Returns the point with the lowest error, or `None` if it is not unique.
+    fn minimum(&self) -> Option<[f64; 3]> {
+        let q = &self.0;
+        let matrix = [[q[0], q[1], q[2]], [q[1], q[4], q[5]], [q[2], q[5], q[7]]];
+        let gradient = [-q[3], -q[6], -q[8]];
+
+        let det = determinant(matrix);
+        if det.abs() < 1e-12 {
+            return None;
+        }
+
+        // Cramer's rule for the gradient being zero
+        Some([0, 1, 2].map(|column| {
+            let mut replaced = matrix;
+            for (row, value) in replaced.iter_mut().zip(gradient) {
+                row[column] = value;
+            }
+            determinant(replaced) / det
+        }))
+    }
+}
+
+impl Add for Quadric {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl AddAssign for Quadric {
+    fn add_assign(&mut self, other: Self) {
+        for (value, other) in self.0.iter_mut().zip(other.0) {
+            *value += other;
+        }
+    }
+}
+
+/// A candidate edge collapse, ordered so that the cheapest collapse is popped first from a [`BinaryHeap`].
+struct Collapse {
+    cost: f64,
+    keep: usize,
+    remove: usize,
+    /// The versions of both vertices when the collapse was computed, outdated collapses are skipped.
+    versions: (usize, usize),
+    position: [f64; 3],
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// The state of a running simplification.
+struct Simplifier {
+    options: SimplifyOptions,
+    positions: Vec<[f64; 3]>,
+    colors: Vec<Option<Color>>,
+    quadrics: Vec<Quadric>,
+    versions: Vec<usize>,
+    faces: Vec<[usize; 3]>,
+    removed_faces: Vec<bool>,
+    vertex_faces: Vec<Vec<usize>>,
+    face_count: usize,
+    /// Scales color differences to the size of the mesh.
+    color_scale: f64,
+    heap: BinaryHeap<Collapse>,
+}
+
+impl Simplifier {
+    fn new(mesh: &Mesh, options: SimplifyOptions) -> Result<Self, Error> {
+        validation::check_faces(mesh, Shape::Triangle)?;
+        let vertex_count = mesh.vertices.len();
+        let faces: Vec<[usize; 3]> = mesh
+            .faces
+            .iter()
+            .map(|face| [face.vertices[0], face.vertices[1], face.vertices[2]])
+            .collect();
+
+        let diagonal = mesh
+            .bounding_box()
+            .map_or(0.0, |(min, max)| f64::from((max - min).length()));
+
+        let mut simplifier = Self {
+            options,
+            positions: mesh
+                .vertices
+                .iter()
+                .map(|vertex| to_f64(vertex.position))
+                .collect(),
+            colors: mesh.vertices.iter().map(|vertex| vertex.color).collect(),
+            quadrics: vec![Quadric::default(); vertex_count],
+            versions: vec![0; vertex_count],
+            removed_faces: vec![false; faces.len()],
+            vertex_faces: vec![Vec::new(); vertex_count],
+            face_count: faces.len(),
+            faces,
+            color_scale: diagonal * diagonal,
+            heap: BinaryHeap::new(),
+        };
+        simplifier.init();
+
+        Ok(simplifier)
+    }
+
+    /// Computes the initial quadrics and collapse candidates.
+    fn init(&mut self) {
+        let mut edges: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+
+        for (index, &face) in self.faces.iter().enumerate() {
+            let normal = self.normal(face);
+            let plane = [
+                normal[0],
+                normal[1],
+                normal[2],
+                -dot(normal, self.positions[face[0]]),
+            ];
+
+            for (i, &vertex) in face.iter().enumerate() {
+                self.quadrics[vertex] += Quadric::plane(plane, 1.0);
+                self.vertex_faces[vertex].push(index);
+
+                let next = face[(i + 1) % 3];
+                edges
+                    .entry((vertex.min(next), vertex.max(next)))
+                    .or_insert((index, 0))
+                    .1 += 1;
+            }
+        }
+
+        if self.options.preserve_boundaries {
+            for (&(a, b), &(face, count)) in &edges {
+                if count == 1 {
+                    self.add_boundary_plane(a, b, face);
+                }
+            }
+        }
+
+        let mut edges: Vec<(usize, usize)> = edges.into_keys().collect();
+        edges.sort_unstable();
+        for (a, b) in edges {
+            self.push_collapse(a, b);
+        }
+    }
+
+    /// Adds a plane perpendicular to the face through the boundary edge to the quadrics of its vertices.
+    fn add_boundary_plane(&mut self, a: usize, b: usize, face: usize) {
+        let normal = self.normal(self.faces[face]);
+        let edge = sub(self.positions[b], self.positions[a]);
+        let perpendicular = normalize(cross(edge, normal));
+        let plane = [
+            perpendicular[0],
+            perpendicular[1],
+            perpendicular[2],
+            -dot(perpendicular, self.positions[a]),
+        ];
+
+        let quadric = Quadric::plane(plane, BOUNDARY_WEIGHT);
+        self.quadrics[a] += quadric;
+        self.quadrics[b] += quadric;
+    }
+
+    /// Computes the best position for collapsing the edge and adds it to the heap.
+    fn push_collapse(&mut self, keep: usize, remove: usize) {
+        let quadric = self.quadrics[keep] + self.quadrics[remove];
+        let (a, b) = (self.positions[keep], self.positions[remove]);
+
+        let position = quadric.minimum().unwrap_or_else(|| {
+            [a, b, lerp(a, b, 0.5)]
+                .into_iter()
+                .min_by(|x, y| quadric.error(*x).total_cmp(&quadric.error(*y)))
+                .unwrap_or(a)
+        });
+
+        let mut cost = quadric.error(position).max(0.0);
+        if self.options.preserve_colors {
+            if let (Some(first), Some(second)) = (self.colors[keep], self.colors[remove]) {
+                cost += color_distance(first, second) * self.color_scale;
+            }
+        }
+
+        self.heap.push(Collapse {
+            cost,
+            keep,
+            remove,
+            versions: (self.versions[keep], self.versions[remove]),
+            position,
+        });
+    }
+
+    /// Collapses edges until the target face count is reached or no collapse is possible.
+    fn run(&mut self, target_face_count: usize) {
+        while self.face_count > target_face_count {
+            let Some(collapse) = self.heap.pop() else {
+                break;
+            };
+
+            if collapse.versions != (self.versions[collapse.keep], self.versions[collapse.remove])
+                || !self.is_valid(&collapse)
+            {
+                continue;
+            }
+
+            self.collapse(&collapse);
+        }
+    }
+
+    /// Returns `true` if the collapse keeps the mesh manifold and does not flip any face.
+    fn is_valid(&self, collapse: &Collapse) -> bool {
+        let (keep, remove) = (collapse.keep, collapse.remove);
+
+        // The link condition: the vertices may only share the neighbours opposite of their shared faces
+        let shared_faces = self.vertex_faces[keep]
+            .iter()
+            .filter(|&&face| self.faces[face].contains(&remove))
+            .count();
+        let keep_neighbours = self.neighbours(keep);
+        let shared_neighbours = self
+            .neighbours(remove)
+            .into_iter()
+            .filter(|neighbour| keep_neighbours.contains(neighbour))
+            .count();
+
+        if shared_faces == 0 || shared_neighbours != shared_faces {
+            return false;
+        }
+
+        self.vertex_faces[keep]
+            .iter()
+            .chain(&self.vertex_faces[remove])
+            .filter(|&&face| {
+                !self.faces[face].contains(&keep) || !self.faces[face].contains(&remove)
+            })
+            .all(|&face| {
+                let before = self.faces[face];
+                let after = before.map(|vertex| {
+                    if vertex == keep || vertex == remove {
+                        collapse.position
+                    } else {
+                        self.positions[vertex]
+                    }
+                });
+
+                let old = cross(
+                    sub(self.positions[before[1]], self.positions[before[0]]),
+                    sub(self.positions[before[2]], self.positions[before[0]]),
+                );
+                let new = cross(sub(after[1], after[0]), sub(after[2], after[0]));
+
+                dot(old, new) > 0.0
+            })
+    }
+
+    /// Merges the removed vertex into the kept vertex.
+    fn collapse(&mut self, collapse: &Collapse) {
+        let (keep, remove) = (collapse.keep, collapse.remove);
+        let (a, b) = (self.positions[keep], self.positions[remove]);
+
+        // Interpolate the color at the projection of the new position onto the edge
+        let edge = sub(b, a);
+        let length = dot(edge, edge);
+        let t = if length > 0.0 {
+            (dot(sub(collapse.position, a), edge) / length).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.colors[keep] = match (self.colors[keep], self.colors[remove]) {
+            (Some(first), Some(second)) => Some(lerp_color(first, second, t)),
+            _ => None,
+        };
+
+        self.positions[keep] = collapse.position;
+        self.quadrics[keep] = self.quadrics[keep] + self.quadrics[remove];
+        self.versions[keep] += 1;
+        self.versions[remove] += 1;
+
+        for face in std::mem::take(&mut self.vertex_faces[remove]) {
+            if self.faces[face].contains(&keep) {
+                self.removed_faces[face] = true;
+                self.face_count -= 1;
+
+                for vertex in self.faces[face] {
+                    self.vertex_faces[vertex].retain(|&other| other != face);
+                }
+            } else {
+                for vertex in &mut self.faces[face] {
+                    if *vertex == remove {
+                        *vertex = keep;
+                    }
+                }
+                self.vertex_faces[keep].push(face);
+            }
+        }
+
+        // Only the costs of the edges around the kept vertex change, validity is checked when a collapse is popped
+        for neighbour in self.neighbours(keep) {
+            self.push_collapse(keep, neighbour);
+        }
+    }
+
+    /// Returns the vertices that share a face with the given vertex.
+    fn neighbours(&self, vertex: usize) -> Vec<usize> {
+        let mut neighbours: Vec<usize> = self.vertex_faces[vertex]
+            .iter()
+            .flat_map(|&face| self.faces[face])
+            .filter(|&other| other != vertex)
+            .collect();
+        neighbours.sort_unstable();
+        neighbours.dedup();
+        neighbours
+    }
+
+    /// Returns the unit normal of a face.
+    fn normal(&self, [a, b, c]: [usize; 3]) -> [f64; 3] {
+        let a = self.positions[a];
+        normalize(cross(sub(self.positions[b], a), sub(self.positions[c], a)))
+    }
+
+    /// Creates the simplified mesh, whose vertices keep their original order.
+    #[allow(clippy::cast_possible_truncation)]
+    fn finish(self, mesh: &Mesh) -> Mesh {
+        let remaining = || {
+            self.faces
+                .iter()
+                .zip(&mesh.faces)
+                .zip(&self.removed_faces)
+                .filter(|(_, &removed)| !removed)
+                .map(|(faces, _)| faces)
+        };
+
+        let mut vertex_map = vec![None; self.positions.len()];
+        for (face, _) in remaining() {
+            for &vertex in face {
+                vertex_map[vertex] = Some(0);
+            }
+        }
+
+        let mut builder = MeshBuilder::with_capacity(0, self.face_count);
+        for (vertex, new) in vertex_map.iter_mut().enumerate() {
+            if new.is_some() {
+                let [x, y, z] = self.positions[vertex];
+                *new = Some(builder.push_vertex(Vertex::new(
+                    Position::new(x as f32, y as f32, z as f32),
+                    self.colors[vertex],
+                )));
+            }
+        }
+
+        for (face, original) in remaining() {
+            builder.add_face_like(
+                face.iter().filter_map(|&vertex| vertex_map[vertex]),
+                original,
+            );
+        }
+
+        builder.build_unchecked()
+    }
+}
+
+fn determinant([a, b, c]: [[f64; 3]; 3]) -> f64 {
+    dot(a, cross(b, c))
+}
+
+fn to_f64(position: Position) -> [f64; 3] {
+    [position.x, position.y, position.z].map(f64::from)
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(a: [f64; 3]) -> [f64; 3] {
+    let length = dot(a, a).sqrt();
+    if length == 0.0 {
+        a
+    } else {
+        a.map(|v| v / length)
+    }
+}
+
+fn lerp(a: [f64; 3], b: [f64; 3], t: f64) -> [f64; 3] {
+    [0, 1, 2].map(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn lerp_color(first: Color, second: Color, t: f64) -> Color {
+    let t = t as f32;
+    let mix = |a: f32, b: f32| (a + (b - a) * t).clamp(0.0, 1.0);
+
+    Color {
+        red: mix(first.red, second.red),
+        green: mix(first.green, second.green),
+        blue: mix(first.blue, second.blue),
+        alpha: mix(first.alpha, second.alpha),
+    }
+}
+
+fn color_distance(first: Color, second: Color) -> f64 {
+    [
+        first.red - second.red,
+        first.green - second.green,
+        first.blue - second.blue,
+        first.alpha - second.alpha,
+    ]
+    .iter()
+    .map(|&v| f64::from(v * v))
+    .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::mesh::Face, primitives};
+
+    fn area(mesh: &Mesh) -> f32 {
+        mesh.faces
+            .iter()
+            .map(|face| {
+                let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[face.vertices[i]].position);
+                (b - a).cross(c - a).length() / 2.0
+            })
+            .sum()
+    }
+
+    /// Asserts that every edge is shared by exactly two faces with opposite directions.
+    fn assert_closed(mesh: &Mesh) {
+        let mut edges = HashMap::new();
+        for face in &mesh.faces {
+            for i in 0..3 {
+                *edges
+                    .entry((face.vertices[i], face.vertices[(i + 1) % 3]))
+                    .or_insert(0) += 1;
+            }
+        }
+        for (&(a, b), &count) in &edges {
+            assert_eq!(count, 1);
+            assert_eq!(edges.get(&(b, a)), Some(&1));
+        }
+    }
+
+    #[test]
+    fn sphere() {
+        let mesh = primitives::icosphere(1.0, 3);
+        let simplified = mesh.simplify(200).unwrap();
+
+        assert!(simplified.face_count() <= 200);
+        assert!(simplified.face_count() > 150);
+        assert_eq!(
+            simplified.vertex_count() + simplified.face_count(),
+            simplified.face_count() * 3 / 2 + 2
+        );
+        assert_closed(&simplified);
+
+        for vertex in &simplified.vertices {
+            assert!((vertex.position.length() - 1.0).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn flat_grid() {
+        let mesh = primitives::plane(4.0, 2.0, 8, 4).triangulate();
+        let simplified = mesh.simplify(2).unwrap();
+
+        assert_eq!(simplified.face_count(), 2);
+        assert_eq!(simplified.bounding_box(), mesh.bounding_box());
+        assert!((area(&simplified) - 8.0).abs() < 1e-4);
+        assert!(simplified
+            .vertices
+            .iter()
+            .all(|vertex| vertex.position.z == 0.0));
+    }
+
+    #[test]
+    fn boundaries() {
+        // A curved boundary shrinks without the boundary planes
+        let mut mesh = primitives::uv_sphere(1.0, 16, 8).triangulate();
+        mesh.faces.retain(|face| {
+            face.vertices
+                .iter()
+                .all(|&vertex| mesh.vertices[vertex].position.z >= -0.01)
+        });
+        let boundary = |mesh: &Mesh| {
+            mesh.vertices
+                .iter()
+                .filter(|vertex| vertex.position.z.abs() < 1e-4)
+                .count()
+        };
+        assert_eq!(boundary(&mesh), 16);
+
+        let preserved = mesh.simplify(40).unwrap();
+        assert_eq!(boundary(&preserved), 16);
+
+        let options = SimplifyOptions {
+            preserve_boundaries: false,
+            ..SimplifyOptions::default()
+        };
+        let free = mesh.simplify_with_options(40, options).unwrap();
+        assert!(boundary(&free) < 16);
+    }
+
+    #[test]
+    fn colors() {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0).unwrap();
+        let blue = Color::new(0.0, 0.0, 1.0, 1.0).unwrap();
+
+        let mut mesh = primitives::plane(4.0, 1.0, 4, 1).triangulate();
+        for vertex in &mut mesh.vertices {
+            vertex.color = Some(if vertex.position.x < 0.5 { red } else { blue });
+        }
+        for face in &mut mesh.faces {
+            face.color = Some(red);
+        }
+
+        let options = SimplifyOptions {
+            preserve_colors: true,
+            ..SimplifyOptions::default()
+        };
+        let simplified = mesh.simplify_with_options(4, options).unwrap();
+        assert_eq!(simplified.face_count(), 4);
+        assert!(simplified
+            .vertices
+            .iter()
+            .all(|vertex| vertex.color == Some(red) || vertex.color == Some(blue)));
+        assert!(simplified.faces.iter().all(|face| face.color == Some(red)));
+
+        let simplified = mesh.simplify(2).unwrap();
+        assert!(simplified
+            .vertices
+            .iter()
+            .all(|vertex| vertex.color.is_some()));
+    }
+
+    #[test]
+    fn unchanged() {
+        let mesh = primitives::platonic(primitives::Platonic::Icosahedron, 1.0);
+        assert_eq!(mesh.simplify(20).unwrap(), mesh);
+        assert_eq!(Mesh::default().simplify(0).unwrap(), Mesh::default());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            primitives::cube(1.0).simplify(2).unwrap_err(),
+            Error::NotATriangle { face: 0, actual: 4 }
+        );
+
+        let mut mesh = primitives::icosphere(1.0, 0);
+        mesh.faces[3] = Face::new([0, 1, 12], None);
+        assert_eq!(
+            mesh.simplify(2).unwrap_err(),
+            Error::VertexOutOfRange {
+                face: 3,
+                vertex: 12,
+                vertex_count: 12
+            }
+        );
+    }
+}
@@ -0,0 +1,566 @@
+use std::collections::HashMap;
+
+use super::{
+    builder::MeshBuilder,
+    color::Color,
+    mesh::{Face, Mesh, Vertex},
+    position::Position,
+    validation::{self, Error, Shape},
+};
+
+impl Mesh {
+    /// Smooths a triangle mesh by applying the given number of Loop subdivision steps.
+    ///
+    /// Every step splits each triangle into four. Edges which are not shared by exactly two faces are treated as
+    /// boundaries, they are split at their midpoint and their vertices only move along the boundary. Vertex colors are
+    /// interpolated with the same weights as the positions, if all contributing vertices have a color. New faces keep
+    /// the color of the face they were split from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a face is not a triangle or references a vertex that does not exist.
+    pub fn subdivide_loop(&self, iterations: usize) -> Result<Mesh, Error> {
+        let mut mesh = self.clone();
+
+        for _ in 0..iterations {
+            mesh = loop_step(&mesh)?;
+        }
+
+        Ok(mesh)
+    }
+
+    /// Smooths a polygon mesh by applying the given number of Catmull-Clark subdivision steps.
+    ///
+    /// Every step splits each face with n vertices into n quads. Boundaries and colors are handled like in
+    /// [`Mesh::subdivide_loop`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a face has less than three vertices or references a vertex that does not exist.
+    pub fn subdivide_catmull_clark(&self, iterations: usize) -> Result<Mesh, Error> {
+        let mut mesh = self.clone();
+
+        for _ in 0..iterations {
+            mesh = catmull_clark_step(&mesh)?;
+        }
+
+        Ok(mesh)
+    }
+}
+
+/// Performs a single Loop subdivision step.
+fn loop_step(mesh: &Mesh) -> Result<Mesh, Error> {
+    validation::check_faces(mesh, Shape::Triangle)?;
+    let topology = Topology::new(mesh);
+
+    let mut vertices: Vec<Vertex> = (0..mesh.vertices.len())
+        .map(|vertex| {
+            topology.smooth_boundary(mesh, vertex).unwrap_or_else(|| {
+                let neighbours = topology.neighbours(vertex);
+                let n = neighbours.len();
+                if n == 0 {
+                    return mesh.vertices[vertex];
+                }
+
+                let beta = if n == 3 {
+                    3.0 / 16.0
+                } else {
+                    3.0 / (8.0 * fraction(n))
+                };
+                let mut blend = Blend::default();
+                blend.add(&mesh.vertices[vertex], 1.0 - fraction(n) * beta);
+                for neighbour in neighbours {
+                    blend.add(&mesh.vertices[neighbour], beta);
+                }
+                blend.finish()
+            })
+        })
+        .collect();
+
+    vertices.extend(topology.edges.iter().map(|edge| {
+        let mut blend = Blend::default();
+
+        if let [first, second] = edge.faces[..] {
+            blend.add(&mesh.vertices[edge.a], 3.0);
+            blend.add(&mesh.vertices[edge.b], 3.0);
+            for face in [first, second] {
+                let opposite = mesh.faces[face]
+                    .vertices
+                    .iter()
+                    .find(|&&vertex| vertex != edge.a && vertex != edge.b);
+                if let Some(&opposite) = opposite {
+                    blend.add(&mesh.vertices[opposite], 1.0);
+                }
+            }
+        } else {
+            blend.add(&mesh.vertices[edge.a], 1.0);
+            blend.add(&mesh.vertices[edge.b], 1.0);
+        }
+
+        blend.finish()
+    }));
+
+    let edge_vertex = |a: usize, b: usize| mesh.vertices.len() + topology.edge(a, b);
+
+    let faces = mesh.faces.iter().flat_map(|face| {
+        let [a, b, c] = [face.vertices[0], face.vertices[1], face.vertices[2]];
+        let (ab, bc, ca) = (edge_vertex(a, b), edge_vertex(b, c), edge_vertex(c, a));
+
+        [[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]]
+            .into_iter()
+            .map(move |vertices| (face, vertices))
+    });
+
+    Ok(assemble(vertices, faces))
+}
+
+/// Performs a single Catmull-Clark subdivision step.
+fn catmull_clark_step(mesh: &Mesh) -> Result<Mesh, Error> {
+    validation::check_faces(mesh, Shape::Polygon)?;
+    let topology = Topology::new(mesh);
+
+    let face_points: Vec<Vertex> = mesh
+        .faces
+        .iter()
+        .map(|face| {
+            let mut blend = Blend::default();
+            for &vertex in &face.vertices {
+                blend.add(&mesh.vertices[vertex], 1.0);
+            }
+            blend.finish()
+        })
+        .collect();
+
+    let mut vertices: Vec<Vertex> = (0..mesh.vertices.len())
+        .map(|vertex| {
+            topology.smooth_boundary(mesh, vertex).unwrap_or_else(|| {
+                let edges = &topology.vertex_edges[vertex];
+                let faces = &topology.vertex_faces[vertex];
+                let n = edges.len();
+                if n < 3 || faces.is_empty() {
+                    return mesh.vertices[vertex];
+                }
+
+                // (Q + 2R + (n - 3)P) / n, where Q is the average of the face points and R the average of the edge
+                // midpoints
+                let mut blend = Blend::default();
+                blend.add(&mesh.vertices[vertex], fraction(n - 3));
+                for &face in faces {
+                    blend.add(&face_points[face], 1.0 / fraction(faces.len()));
+                }
+                for &edge in edges {
+                    let edge = &topology.edges[edge];
+                    let weight = 1.0 / fraction(n);
+                    blend.add(&mesh.vertices[edge.a], weight);
+                    blend.add(&mesh.vertices[edge.b], weight);
+                }
+                blend.finish()
+            })
+        })
+        .collect();
+
+    let face_offset = vertices.len();
+    vertices.extend_from_slice(&face_points);
+
+    let edge_offset = vertices.len();
+    vertices.extend(topology.edges.iter().map(|edge| {
+        let mut blend = Blend::default();
+        blend.add(&mesh.vertices[edge.a], 1.0);
+        blend.add(&mesh.vertices[edge.b], 1.0);
+
+        if let [first, second] = edge.faces[..] {
+            blend.add(&face_points[first], 1.0);
+            blend.add(&face_points[second], 1.0);
+        }
+
+        blend.finish()
+    }));
+
+    let edge_vertex = |a: usize, b: usize| edge_offset + topology.edge(a, b);
+
+    let faces = mesh.faces.iter().enumerate().flat_map(|(index, face)| {
+        let n = face.vertices.len();
+
+        (0..n).map(move |i| {
+            let previous = face.vertices[(i + n - 1) % n];
+            let current = face.vertices[i];
+            let next = face.vertices[(i + 1) % n];
+
+            (
+                face,
+                [
+                    current,
+                    edge_vertex(current, next),
+                    face_offset + index,
+                    edge_vertex(previous, current),
+                ],
+            )
+        })
+    });
+
+    Ok(assemble(vertices, faces))
+}
+
+/// Creates a mesh from the new vertices and faces, which keep the color of the face they were split from.
+fn assemble<'f, F, const N: usize>(vertices: Vec<Vertex>, faces: F) -> Mesh
+where
+    F: Iterator<Item = (&'f Face, [usize; N])>,
+{
+    let mut builder = MeshBuilder::new();
+    builder.extend_vertices(vertices);
+    for (parent, vertices) in faces {
+        builder.add_face_like(vertices, parent);
+    }
+
+    builder.build_unchecked()
+}
+
+/// An undirected edge and the faces that contain it.
+struct Edge {
+    a: usize,
+    b: usize,
+    faces: Vec<usize>,
+}
+
+impl Edge {
+    /// Returns `true` if the edge is not shared by exactly two faces.
+    fn is_boundary(&self) -> bool {
+        self.faces.len() != 2
+    }
+}
+
+/// The adjacency information of a mesh required for subdivision.
+struct Topology {
+    edges: Vec<Edge>,
+    edge_ids: HashMap<(usize, usize), usize>,
+    vertex_edges: Vec<Vec<usize>>,
+    vertex_faces: Vec<Vec<usize>>,
+}
+
+impl Topology {
+    /// Collects the edges of the mesh, whose faces must only reference existing vertices.
+    fn new(mesh: &Mesh) -> Self {
+        let mut topology = Self {
+            edges: Vec::new(),
+            edge_ids: HashMap::new(),
+            vertex_edges: vec![Vec::new(); mesh.vertices.len()],
+            vertex_faces: vec![Vec::new(); mesh.vertices.len()],
+        };
+
+        for (index, face) in mesh.faces.iter().enumerate() {
+            let vertices = &face.vertices;
+
+            for (i, &a) in vertices.iter().enumerate() {
+                topology.vertex_faces[a].push(index);
+
+                let b = vertices[(i + 1) % vertices.len()];
+                let key = (a.min(b), a.max(b));
+                let id = *topology.edge_ids.entry(key).or_insert_with(|| {
+                    topology.edges.push(Edge {
+                        a: key.0,
+                        b: key.1,
+                        faces: Vec::new(),
+                    });
+                    topology.edges.len() - 1
+                });
+                topology.edges[id].faces.push(index);
+            }
+        }
+
+        for (id, edge) in topology.edges.iter().enumerate() {
+            topology.vertex_edges[edge.a].push(id);
+            topology.vertex_edges[edge.b].push(id);
+        }
+
+        topology
+    }
+
+    /// Returns the index of the edge between the given vertices.
+    fn edge(&self, a: usize, b: usize) -> usize {
+        self.edge_ids[&(a.min(b), a.max(b))]
+    }
+
+    /// Returns the vertices which share an edge with the given vertex.
+    fn neighbours(&self, vertex: usize) -> impl ExactSizeIterator<Item = usize> + '_ {
+        self.vertex_edges[vertex].iter().map(move |&edge| {
+            let edge = &self.edges[edge];
+            if edge.a == vertex {
+                edge.b
+            } else {
+                edge.a
+            }
+        })
+    }
+
+    /// Returns the new position of a boundary vertex, or `None` if the vertex is not on a boundary.
+    ///
+    /// A vertex on a regular boundary moves towards its two boundary neighbours. Vertices where more than two boundary
+    /// edges meet are corners and stay in place.
+    fn smooth_boundary(&self, mesh: &Mesh, vertex: usize) -> Option<Vertex> {
+        let boundary: Vec<usize> = self
+            .neighbours(vertex)
+            .zip(&self.vertex_edges[vertex])
+            .filter(|&(_, &edge)| self.edges[edge].is_boundary())
+            .map(|(neighbour, _)| neighbour)
+            .collect();
+
+        match boundary[..] {
+            [] => None,
+            [first, second] => {
+                let mut blend = Blend::default();
+                blend.add(&mesh.vertices[vertex], 6.0);
+                blend.add(&mesh.vertices[first], 1.0);
+                blend.add(&mesh.vertices[second], 1.0);
+                Some(blend.finish())
+            }
+            _ => Some(mesh.vertices[vertex]),
+        }
+    }
+}
+
+/// Accumulates a weighted average of vertices.
+///
+/// The color is only interpolated if every vertex with a non-zero weight has a color.
+#[derive(Default)]
+struct Blend {
+    position: Position,
+    color: [f32; 4],
+    colored: Option<bool>,
+    weight: f32,
+}
+
+impl Blend {
+    fn add(&mut self, vertex: &Vertex, weight: f32) {
+        if weight == 0.0 {
+            return;
+        }
+
+        self.position = self.position + vertex.position * weight;
+        self.weight += weight;
+
+        match vertex.color {
+            Some(color) if self.colored != Some(false) => {
+                self.colored = Some(true);
+                for (sum, value) in
+                    self.color
+                        .iter_mut()
+                        .zip([color.red, color.green, color.blue, color.alpha])
+                {
+                    *sum += value * weight;
+                }
+            }
+            _ => self.colored = Some(false),
+        }
+    }
+
+    fn finish(self) -> Vertex {
+        let scale = 1.0 / self.weight;
+        let color = (self.colored == Some(true)).then(|| {
+            let [red, green, blue, alpha] = self.color.map(|sum| (sum * scale).clamp(0.0, 1.0));
+            Color {
+                red,
+                green,
+                blue,
+                alpha,
+            }
+        });
+
+        Vertex::new(self.position * scale, color)
+    }
+}
+
+/// Converts a count to a float for use as a weight.
+#[allow(clippy::cast_precision_loss)]
+fn fraction(count: usize) -> f32 {
+    count as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{self, Platonic};
+
+    fn assert_close(actual: Position, expected: Position) {
+        assert!(
+            (actual - expected).length() < 1e-5,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    fn edge_count(mesh: &Mesh) -> usize {
+        Topology::new(mesh).edges.len()
+    }
+
+    #[test]
+    fn loop_closed() {
+        let mesh = primitives::platonic(Platonic::Icosahedron, 1.0);
+        let subdivided = mesh.subdivide_loop(2).unwrap();
+
+        assert_eq!(subdivided.face_count(), 20 * 16);
+        assert_eq!(
+            subdivided.vertex_count() + subdivided.face_count(),
+            edge_count(&subdivided) + 2
+        );
+        assert!(Topology::new(&subdivided)
+            .edges
+            .iter()
+            .all(|edge| !edge.is_boundary()));
+
+        // Loop subdivision shrinks convex shapes towards their limit surface
+        for vertex in &subdivided.vertices {
+            let length = vertex.position.length();
+            assert!(length > 0.7 && length < 0.8, "{length}");
+        }
+    }
+
+    #[test]
+    fn loop_boundary() {
+        let mut mesh = Mesh::default();
+        for position in [
+            Position::new(0.0, 0.0, 0.0),
+            Position::new(8.0, 0.0, 0.0),
+            Position::new(0.0, 8.0, 0.0),
+        ] {
+            mesh.vertices.push(Vertex::new(position, None));
+        }
+        mesh.faces.push(Face::new([0, 1, 2], None));
+
+        let subdivided = mesh.subdivide_loop(1).unwrap();
+        assert_eq!((subdivided.vertex_count(), subdivided.face_count()), (6, 4));
+        assert_close(
+            subdivided.vertices[0].position,
+            Position::new(1.0, 1.0, 0.0),
+        );
+        assert_close(
+            subdivided.vertices[1].position,
+            Position::new(6.0, 1.0, 0.0),
+        );
+        assert_close(
+            subdivided.vertices[3].position,
+            Position::new(4.0, 0.0, 0.0),
+        );
+        assert_eq!(subdivided.faces[0], Face::new([0, 3, 5], None));
+    }
+
+    #[test]
+    fn loop_requires_triangles() {
+        assert_eq!(
+            primitives::cube(1.0).subdivide_loop(1).unwrap_err(),
+            Error::NotATriangle { face: 0, actual: 4 }
+        );
+        assert_eq!(
+            primitives::cube(1.0).subdivide_loop(0).unwrap(),
+            primitives::cube(1.0)
+        );
+    }
+
+    #[test]
+    fn catmull_clark_cube() {
+        let mesh = primitives::cube(2.0);
+        let subdivided = mesh.subdivide_catmull_clark(1).unwrap();
+
+        assert_eq!(subdivided.vertex_count(), 8 + 6 + 12);
+        assert_eq!(subdivided.face_count(), 24);
+        assert!(subdivided.faces.iter().all(|face| face.vertices.len() == 4));
+
+        let corner = 5.0 / 9.0;
+        assert_close(
+            subdivided.vertices[7].position,
+            Position::new(corner, corner, corner),
+        );
+        assert_close(
+            subdivided.vertices[8].position,
+            Position::new(0.0, 0.0, -1.0),
+        );
+
+        // The edge between (1, 1, -1) and (1, 1, 1)
+        let edge = Topology::new(&mesh).edge(3, 7);
+        assert_close(
+            subdivided.vertices[14 + edge].position,
+            Position::new(0.75, 0.75, 0.0),
+        );
+
+        let twice = mesh.subdivide_catmull_clark(2).unwrap();
+        assert_eq!(twice.face_count(), 96);
+        assert_eq!(
+            twice.vertex_count() + twice.face_count(),
+            edge_count(&twice) + 2
+        );
+    }
+
+    #[test]
+    fn catmull_clark_boundary() {
+        let mesh = primitives::plane(2.0, 2.0, 1, 1);
+        let subdivided = mesh.subdivide_catmull_clark(1).unwrap();
+
+        assert_eq!((subdivided.vertex_count(), subdivided.face_count()), (9, 4));
+        assert_close(
+            subdivided.vertices[0].position,
+            Position::new(-0.75, -0.75, 0.0),
+        );
+        assert_close(subdivided.vertices[4].position, Position::default());
+        assert_close(
+            subdivided.vertices[5].position,
+            Position::new(0.0, -1.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn catmull_clark_mixed_faces() {
+        let mesh = primitives::cone(1.0, 1.0, 5);
+        let subdivided = mesh.subdivide_catmull_clark(1).unwrap();
+
+        assert_eq!(subdivided.face_count(), 5 * 3 + 5);
+        assert_eq!(
+            subdivided.vertex_count() + subdivided.face_count(),
+            edge_count(&subdivided) + 2
+        );
+    }
+
+    #[test]
+    fn colors() {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0).unwrap();
+        let blue = Color::new(0.0, 0.0, 1.0, 1.0).unwrap();
+
+        let mut mesh = primitives::plane(1.0, 1.0, 1, 1);
+        mesh.faces[0].color = Some(blue);
+        for (i, vertex) in mesh.vertices.iter_mut().enumerate() {
+            vertex.color = Some(if i < 2 { red } else { blue });
+        }
+
+        let subdivided = mesh.subdivide_catmull_clark(1).unwrap();
+        assert!(subdivided.faces.iter().all(|face| face.color == Some(blue)));
+        assert_eq!(
+            subdivided.vertices[4].color,
+            Some(Color::new(0.5, 0.0, 0.5, 1.0).unwrap())
+        );
+        let corner = Color::new(0.875, 0.0, 0.125, 1.0).ok();
+        assert_eq!(subdivided.vertices[0].color, corner);
+
+        // Vertex 3 only contributes to the face point and the vertices on its edges
+        mesh.vertices[3].color = None;
+        let subdivided = mesh.subdivide_catmull_clark(1).unwrap();
+        assert_eq!(subdivided.vertices[4].color, None);
+        assert_eq!(subdivided.vertices[0].color, corner);
+    }
+
+    #[test]
+    fn errors() {
+        let mut mesh = primitives::plane(1.0, 1.0, 1, 1);
+        mesh.faces.push(Face::new([0, 1], None));
+        assert_eq!(
+            mesh.subdivide_catmull_clark(1).unwrap_err(),
+            Error::NotEnoughVertices { face: 1, actual: 2 }
+        );
+
+        mesh.faces[1] = Face::new([0, 1, 4], None);
+        assert_eq!(
+            mesh.subdivide_catmull_clark(1).unwrap_err(),
+            Error::VertexOutOfRange {
+                face: 1,
+                vertex: 4,
+                vertex_count: 4
+            }
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use super::{
+    builder::MeshBuilder,
+    mesh::Mesh,
+    validation::{self, Error, Shape},
+};
+
+/// A part of a [`Mesh`] extracted by [`Mesh::submesh`].
+///
+/// The submesh only contains the vertices referenced by the selected faces, in the order of their original indices.
+/// The remap tables translate vertex indices between the original mesh and the submesh.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Submesh {
+    /// The extracted mesh.
+    pub mesh: Mesh,
+    /// The new index of every vertex of the original mesh, or `None` if the vertex is not part of the submesh.
+    pub vertex_map: Vec<Option<usize>>,
+    /// The original index of every vertex of the submesh.
+    pub original_vertices: Vec<usize>,
+}
+
+impl Submesh {
+    /// Returns the index in the submesh of the vertex with the given index in the original mesh.
+    #[must_use]
+    pub fn remap(&self, original: usize) -> Option<usize> {
+        self.vertex_map.get(original).copied().flatten()
+    }
+}
+
+impl Mesh {
+    /// Extracts the faces with the given indices, in the given order, into a new mesh.
+    ///
+    /// Unreferenced vertices are dropped and the remaining ones are renumbered, see [`Submesh`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a face index is out of range or a selected face references a vertex that does not
+    /// exist.
+    pub fn submesh<I: IntoIterator<Item = usize>>(&self, faces: I) -> Result<Submesh, Error> {
+        let mut extractor = Extractor::new(self);
+        let mesh = extractor.extract(faces)?;
+
+        Ok(Submesh {
+            mesh,
+            vertex_map: extractor.vertex_map,
+            original_vertices: extractor.original_vertices,
+        })
+    }
+}
+
+/// Extracts faces of a [`Mesh`] into new meshes, reusing the vertex remap table between extractions.
+///
+/// Every extraction only touches the entries of the vertices it references, so splitting a mesh into many parts does
+/// not scan all vertices per part.
+pub(crate) struct Extractor<'m> {
+    mesh: &'m Mesh,
+    /// The new index of every vertex referenced by the last extraction.
+    vertex_map: Vec<Option<usize>>,
+    /// The original index of every vertex of the last extraction.
+    original_vertices: Vec<usize>,
+}
+
+impl<'m> Extractor<'m> {
+    pub(crate) fn new(mesh: &'m Mesh) -> Self {
+        Self {
+            mesh,
+            vertex_map: vec![None; mesh.vertices.len()],
+            original_vertices: Vec::new(),
+        }
+    }
+
+    /// Extracts the faces with the given indices, in the given order, into a new mesh.
+    pub(crate) fn extract<I: IntoIterator<Item = usize>>(
+        &mut self,
+        faces: I,
+    ) -> Result<Mesh, Error> {
+        for &vertex in &self.original_vertices {
+            self.vertex_map[vertex] = None;
+        }
+        self.original_vertices.clear();
+
+        let faces = faces.into_iter();
+        let mut selected = Vec::with_capacity(faces.size_hint().0);
+
+        for index in faces {
+            let face = self.mesh.faces.get(index).ok_or(Error::FaceOutOfRange {
+                face: index,
+                face_count: self.mesh.faces.len(),
+            })?;
+
+            validation::check_face(index, &face.vertices, self.vertex_map.len(), Shape::Any)?;
+
+            for &vertex in &face.vertices {
+                let new = &mut self.vertex_map[vertex];
+                if new.is_none() {
+                    *new = Some(0);
+                    self.original_vertices.push(vertex);
+                }
+            }
+
+            selected.push(face);
+        }
+
+        // Number the referenced vertices in their original order
+        self.original_vertices.sort_unstable();
+        for (new, &original) in self.original_vertices.iter().enumerate() {
+            self.vertex_map[original] = Some(new);
+        }
+
+        let mut builder = MeshBuilder::with_capacity(self.original_vertices.len(), selected.len());
+        builder.extend_vertices(
+            self.original_vertices
+                .iter()
+                .map(|&original| self.mesh.vertices[original]),
+        );
+        for face in selected {
+            builder.add_face_like(
+                face.vertices
+                    .iter()
+                    .filter_map(|&vertex| self.vertex_map[vertex]),
+                face,
+            );
+        }
+
+        Ok(builder.build_unchecked())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{
+        color::Color,
+        mesh::{Face, Vertex},
+        position::Position,
+    };
+
+    fn strip() -> Mesh {
+        Mesh {
+            vertices: (0..6_u8)
+                .map(|i| Vertex::new(Position::new(f32::from(i), 0.0, 0.0), None))
+                .collect(),
+            faces: vec![
+                Face::new(vec![0, 1, 2], None),
+                Face::new(vec![1, 3, 2], None),
+                Face::new(vec![2, 3, 4, 5], Color::new(1.0, 0.0, 0.0, 1.0).ok()),
+            ],
+        }
+    }
+
+    #[test]
+    fn submesh() {
+        let mesh = strip();
+        let submesh = mesh.submesh([2, 1]).unwrap();
+
+        assert_eq!(submesh.original_vertices, vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            submesh.vertex_map,
+            vec![None, Some(0), Some(1), Some(2), Some(3), Some(4)]
+        );
+        assert_eq!(submesh.remap(3), Some(2));
+        assert_eq!(submesh.remap(0), None);
+        assert_eq!(submesh.remap(6), None);
+
+        assert_eq!(submesh.mesh.vertices[..], mesh.vertices[1..]);
+        assert_eq!(
+            submesh.mesh.faces,
+            vec![
+                Face::new(vec![1, 2, 3, 4], mesh.faces[2].color),
+                Face::new(vec![0, 2, 1], None)
+            ]
+        );
+    }
+
+    #[test]
+    fn whole_mesh() {
+        let mesh = strip();
+        let submesh = mesh.submesh(0..mesh.face_count()).unwrap();
+        assert_eq!(submesh.mesh, mesh);
+        assert_eq!(submesh.original_vertices, (0..6).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn empty() {
+        let submesh = strip().submesh([]).unwrap();
+        assert_eq!(submesh.mesh, Mesh::default());
+        assert_eq!(submesh.vertex_map, vec![None; 6]);
+    }
+
+    #[test]
+    fn errors() {
+        let mut mesh = strip();
+        assert_eq!(
+            mesh.submesh([0, 3]).unwrap_err(),
+            Error::FaceOutOfRange {
+                face: 3,
+                face_count: 3
+            }
+        );
+
+        mesh.faces[1] = Face::new(vec![1, 6, 2], None);
+        assert_eq!(
+            mesh.submesh([1]).unwrap_err(),
+            Error::VertexOutOfRange {
+                face: 1,
+                vertex: 6,
+                vertex_count: 6
+            }
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt::{Debug, Display, Formatter};
+
+use super::mesh::Mesh;
+
+/// Contains errors that occur if the faces of a [`Mesh`] do not meet the requirements of an operation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// The selected face does not exist.
+    FaceOutOfRange {
+        /// The selected face index.
+        face: usize,
+        /// The number of faces of the mesh.
+        face_count: usize,
+    },
+    /// The face has less than three vertices.
+    NotEnoughVertices {
+        /// The index of the face.
+        face: usize,
+        /// The number of vertices of the face.
+        actual: usize,
+    },
+    /// The operation requires triangles, but the face has a different number of vertices.
+    NotATriangle {
+        /// The index of the face.
+        face: usize,
+        /// The number of vertices of the face.
+        actual: usize,
+    },
+    /// The face references a vertex that does not exist.
+    VertexOutOfRange {
+        /// The index of the face.
+        face: usize,
+        /// The referenced vertex index.
+        vertex: usize,
+        /// The number of vertices of the mesh.
+        vertex_count: usize,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FaceOutOfRange { face, face_count } => write!(
+                f,
+                "Face {face} was selected, but the mesh only has {face_count} faces"
+            ),
+            Self::NotEnoughVertices { face, actual } => write!(
+                f,
+                "Face {face} has {actual} vertices, but at least three are required"
+            ),
+            Self::NotATriangle { face, actual } => write!(
+                f,
+                "Face {face} has {actual} vertices, but the operation requires triangles"
+            ),
+            Self::VertexOutOfRange {
+                face,
+                vertex,
+                vertex_count,
+            } => write!(
+                f,
+                "Face {face} references vertex {vertex}, but the mesh only has {vertex_count} vertices"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The number of vertices an operation requires per face.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Shape {
+    /// Faces may have any number of vertices.
+    Any,
+    /// Faces need at least three vertices.
+    Polygon,
+    /// Faces need exactly three vertices.
+    Triangle,
+}
+
+/// Checks that the face with the given index and vertices has the required shape and only references vertices below
+/// `vertex_count`.
+pub(crate) fn check_face(
+    face: usize,
+    vertices: &[usize],
+    vertex_count: usize,
+    shape: Shape,
+) -> Result<(), Error> {
+    let actual = vertices.len();
+
+    match shape {
+        Shape::Polygon if actual < 3 => return Err(Error::NotEnoughVertices { face, actual }),
+        Shape::Triangle if actual != 3 => return Err(Error::NotATriangle { face, actual }),
+        _ => {}
+    }
+
+    match vertices.iter().find(|&&vertex| vertex >= vertex_count) {
+        Some(&vertex) => Err(Error::VertexOutOfRange {
+            face,
+            vertex,
+            vertex_count,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Checks every face of the mesh with [`check_face`].
+pub(crate) fn check_faces(mesh: &Mesh, shape: Shape) -> Result<(), Error> {
+    mesh.faces
+        .iter()
+        .enumerate()
+        .try_for_each(|(index, face)| check_face(index, &face.vertices, mesh.vertices.len(), shape))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_face() {
+        assert_eq!(super::check_face(0, &[0, 1], 3, Shape::Any), Ok(()));
+        assert_eq!(
+            super::check_face(1, &[0, 1], 3, Shape::Polygon),
+            Err(Error::NotEnoughVertices { face: 1, actual: 2 })
+        );
+        assert_eq!(
+            super::check_face(2, &[0, 1, 2, 0], 3, Shape::Triangle),
+            Err(Error::NotATriangle { face: 2, actual: 4 })
+        );
+        assert_eq!(
+            super::check_face(3, &[0, 5, 2], 3, Shape::Triangle),
+            Err(Error::VertexOutOfRange {
+                face: 3,
+                vertex: 5,
+                vertex_count: 3
+            })
+        );
+        assert_eq!(
+            Error::VertexOutOfRange {
+                face: 3,
+                vertex: 5,
+                vertex_count: 3
+            }
+            .to_string(),
+            "Face 3 references vertex 5, but the mesh only has 3 vertices"
+        );
+    }
+}
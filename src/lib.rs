@@ -20,17 +20,33 @@
 //!);
 //! ```
 
+pub mod bvh;
+pub mod compression;
+pub mod dataset;
 pub mod geometry;
 pub mod parser;
+pub mod primitives;
+pub mod sampling;
+pub mod stl;
+pub mod voxel;
+pub mod writer;
 
+use crate::geometry::compact::CompactMesh;
 use crate::geometry::mesh::Mesh;
-use crate::parser::options::Options;
+use crate::parser::color_format::ColorFormat;
+use crate::parser::error::Error as ParserError;
+use crate::parser::options::{Limit, Options};
 use crate::parser::Parser;
-use std::fs::File;
-use std::io::{self, Read};
-use std::path::Path;
+use crate::writer::Writer;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
 
 /// Contains errors that occur during parsing.
+///
+/// Errors returned by functions operating on files (e.g. [`from_path`]) contain the path of the file, see
+/// [`Error::path`]. The message of the wrapped error is part of the [`Display`](std::fmt::Display) output, so
+/// [`std::error::Error::source`] skips it and returns its cause (e.g. the [`std::num::ParseFloatError`] of a parser
+/// error) to avoid printing the message twice when walking the chain.
 #[derive(Debug)]
 pub enum Error {
     /// An IO error occurred while reading the file.
@@ -39,13 +55,48 @@ pub enum Error {
     ParserError(crate::parser::error::Error),
 }
 
-impl std::error::Error for Error {}
+impl Error {
+    /// Returns the path of the file the error belongs to, if it occurred while reading or writing a file.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Error::IOError(e) => e
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<PathError>())
+                .map(|inner| inner.path.as_path()),
+            Error::ParserError(e) => e.path(),
+        }
+    }
+
+    /// Attaches the path of the file the error belongs to.
+    fn with_path(self, path: &Path) -> Self {
+        match self {
+            Error::IOError(e) => Error::IOError(io::Error::new(
+                e.kind(),
+                PathError {
+                    path: path.to_path_buf(),
+                    source: e,
+                },
+            )),
+            Error::ParserError(e) => Error::ParserError(e.with_path(path)),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IOError(e) => e.source(),
+            Error::ParserError(e) => e.source(),
+        }
+    }
+}
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            Error::IOError(e) => write!(f, "IO Error: {}", e),
-            Error::ParserError(e) => write!(f, "Parser Error: {}", e),
+            Error::IOError(e) => write!(f, "IO Error: {e}"),
+            Error::ParserError(e) => write!(f, "Parser Error: {e}"),
         }
     }
 }
@@ -62,24 +113,185 @@ impl From<crate::parser::error::Error> for Error {
     }
 }
 
+/// An [`io::Error`] that occurred while reading or writing the file at `path`.
+///
+/// It is wrapped in an [`io::Error`] of the same [`io::ErrorKind`] to keep [`Error::IOError`] unchanged.
+#[derive(Debug)]
+struct PathError {
+    path: PathBuf,
+    source: io::Error,
+}
+
+impl std::error::Error for PathError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.source()
+    }
+}
+
+impl std::fmt::Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.source)
+    }
+}
+
 /// This result may contain the parsed [`crate::geometry::mesh::Mesh`] or the [`self::Result`] that occurred.
 pub type Result<D = Mesh> = std::result::Result<D, Error>;
 
 /// Parse a [`crate::geometry::mesh::Mesh`] from a [`std::path::Path`] pointing to an `.off` file.
 ///
+/// Compressed files (e.g. `.off.gz`) are decompressed transparently if the feature of their
+/// [`Compression`](`crate::compression::Compression`) format is enabled.
+///
+/// At most [`Limits::byte_count`](`crate::parser::options::Limits`) bytes of decompressed data are read into memory.
+///
 /// # Errors
 ///
 /// Will return `self::Error` if an error occurs while reading the file or parsing the `off` data.
 pub fn from_path<P: AsRef<Path>>(path: P, options: Options) -> Result {
-    let mut file = File::open(path).map_err(Error::IOError)?;
+    let path = path.as_ref();
 
-    let mut string = String::new();
-    match file.read_to_string(&mut string) {
-        Ok(_) => {}
-        Err(inner) => return Err(Error::IOError(inner)),
+    let parse_file = || -> Result {
+        let string = read_limited(compression::open(path)?, options)?;
+        parse(&string, options)
     };
 
-    parse(&string, options)
+    parse_file().map_err(|err| err.with_path(path))
+}
+
+/// Parse a [`crate::geometry::mesh::Mesh`] from a [`std::path::Path`] pointing to an `.off` file by memory-mapping it.
+///
+/// Unlike [`from_path`] the file is not copied into a heap allocated [`String`]; the mapped bytes are validated as
+/// UTF-8 in place and handed to the parser directly.
+///
+/// The file must not be modified by another process while it is parsed.
+/// Compressed files are decompressed from the mapped bytes into a [`String`] first.
+///
+/// # Errors
+///
+/// Will return `self::Error` if an error occurs while mapping the file, if it is not valid UTF-8 or if an error occurs
+/// while parsing the `off` data.
+#[cfg(feature = "mmap")]
+pub fn from_path_mmap<P: AsRef<Path>>(path: P, options: Options) -> Result {
+    use crate::compression::Compression;
+
+    let path = path.as_ref();
+
+    let parse_file = || -> Result {
+        let file = std::fs::File::open(path)?;
+
+        // SAFETY: The map is only read while parsing and does not outlive this function. Concurrent modification of
+        // the file by other processes is documented as unsupported above.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+
+        let compression = Compression::detect(&map);
+        if compression != Compression::None {
+            let string = read_limited(compression.decoder(&map[..])?, options)?;
+
+            return parse(&string, options);
+        }
+
+        let string = std::str::from_utf8(&map)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        parse(string, options)
+    };
+
+    parse_file().map_err(|err| err.with_path(path))
+}
+
+/// Parse a [`crate::geometry::mesh::Mesh`] from a [`tokio::io::AsyncRead`] providing `off` data.
+///
+/// The data is read in chunks without blocking the runtime and returns the same result as [`parse`]. Once it is
+/// complete, it is decompressed like in [`from_path`] and parsed on a thread of the blocking pool (see
+/// [`tokio::task::spawn_blocking`]), so large meshes do not stall other tasks.
+///
+/// At most [`Limits::byte_count`](`crate::parser::options::Limits`) bytes are read into memory, both before and after
+/// decompression.
+///
+/// # Errors
+///
+/// Will return `self::Error` if an error occurs while reading the data, if it is not valid UTF-8 or if an error occurs
+/// while parsing the `off` data.
+///
+/// # Panics
+///
+/// Panics if called outside of a Tokio runtime.
+#[cfg(feature = "tokio")]
+pub async fn from_async_reader<R>(mut reader: R, options: Options) -> Result
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    use crate::compression::Compression;
+    use tokio::io::AsyncReadExt;
+
+    let max = options.limits.byte_count;
+
+    let mut bytes = Vec::new();
+    (&mut reader)
+        .take(u64::try_from(max).unwrap_or(u64::MAX).saturating_add(1))
+        .read_to_end(&mut bytes)
+        .await?;
+
+    if bytes.len() > max {
+        return Err(byte_count_exceeded(max));
+    }
+
+    let task = tokio::task::spawn_blocking(move || {
+        let string = read_limited(Compression::detect(&bytes).decoder(&bytes[..])?, options)?;
+
+        parse(&string, options)
+    });
+
+    match task.await {
+        Ok(result) => result,
+        Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+        Err(err) => Err(io::Error::other(err).into()),
+    }
+}
+
+/// Parse a [`crate::geometry::mesh::Mesh`] from a [`std::path::Path`] pointing to an `.off` file without blocking the
+/// runtime.
+///
+/// This is the asynchronous counterpart of [`from_path`] and returns the same result.
+///
+/// # Errors
+///
+/// Will return `self::Error` if an error occurs while reading the file or parsing the `off` data.
+///
+/// # Panics
+///
+/// Panics if called outside of a Tokio runtime.
+#[cfg(feature = "tokio")]
+pub async fn from_path_async<P: AsRef<Path>>(path: P, options: Options) -> Result {
+    let path = path.as_ref();
+
+    let result: Result = async {
+        let file = tokio::fs::File::open(path).await?;
+        from_async_reader(tokio::io::BufReader::new(file), options).await
+    }
+    .await;
+
+    result.map_err(|err| err.with_path(path))
+}
+
+/// Reads the `off` data from `reader` into a [`String`] without reading more than
+/// [`Limits::byte_count`](`crate::parser::options::Limits`) bytes into memory.
+///
+/// Reading stops as soon as the data exceeds the limit, so the rest of a decompression bomb or an endless stream is
+/// never read.
+fn read_limited<R: Read>(reader: R, options: Options) -> Result<String> {
+    let max = options.limits.byte_count;
+    let bytes = compression::read_at_most(reader, max)?.ok_or_else(|| byte_count_exceeded(max))?;
+
+    String::from_utf8(bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err).into())
+}
+
+/// Creates the error returned if the `off` data exceeds [`Limits::byte_count`](`crate::parser::options::Limits`).
+///
+/// The actual size is unknown because reading stopped at the first byte beyond the limit.
+fn byte_count_exceeded(max: usize) -> Error {
+    let actual = max.saturating_add(1);
+    ParserError::limit_exceeded(0, Limit::ByteCount, max, actual, "Input size exceeds limit").into()
 }
 
 /// Directly parse a [`crate::geometry::mesh::Mesh`] from an `off` string.
@@ -110,3 +322,77 @@ pub fn from_path<P: AsRef<Path>>(path: P, options: Options) -> Result {
 pub fn parse(string: &str, options: Options) -> Result {
     Parser::new(&string, options).parse()
 }
+
+/// Directly parse a [`crate::geometry::compact::CompactMesh`] from an `off` string.
+///
+/// The data is parsed into the compact representation without creating a [`crate::geometry::mesh::Mesh`] first.
+///
+/// # Errors
+///
+/// Will return `self::Error` if an error occurs while parsing the `off` data or if the indices do not fit into an
+/// [`u32`].
+pub fn parse_compact(string: &str, options: Options) -> Result<CompactMesh> {
+    Parser::new(&string, options).parse_compact()
+}
+
+/// Write a [`crate::geometry::mesh::Mesh`] as `off` data to the file at `path`.
+///
+/// Colors are written in the given [`ColorFormat`].
+/// The data is compressed based on the extension of `path` (e.g. `.off.gz`), see [`compression::create`].
+///
+/// # Errors
+///
+/// Will return `self::Error` if an error occurs while writing the file.
+pub fn to_path<P: AsRef<Path>>(mesh: &Mesh, path: P, color_format: ColorFormat) -> Result<()> {
+    let path = path.as_ref();
+
+    let write_file = || -> Result<()> {
+        let mut encoder = compression::create(path)?;
+        Writer::new(mesh, color_format).write(&mut encoder)?;
+        encoder.finish()?.flush()?;
+
+        Ok(())
+    };
+
+    write_file().map_err(|err| err.with_path(path))
+}
+
+/// Write a [`crate::geometry::mesh::Mesh`] as `off` data to a [`String`].
+///
+/// Colors are written in the given [`ColorFormat`].
+///
+/// # Errors
+///
+/// Will return `self::Error` if a color cannot be represented in the [`ColorFormat`].
+pub fn to_string(mesh: &Mesh, color_format: ColorFormat) -> Result<String> {
+    Ok(Writer::new(mesh, color_format).write_string()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{error::Details, options::Limits};
+
+    #[test]
+    fn read_limited_endless_reader() {
+        let options = Options {
+            limits: Limits {
+                byte_count: 1024,
+                ..Limits::default()
+            },
+            ..Options::default()
+        };
+
+        let Err(Error::ParserError(err)) = read_limited(io::repeat(b' '), options) else {
+            panic!("expected a parser error");
+        };
+        assert_eq!(
+            err.details,
+            Details::LimitExceeded {
+                limit: Limit::ByteCount,
+                max: 1024,
+                actual: 1025,
+            }
+        );
+    }
+}
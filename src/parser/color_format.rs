@@ -9,13 +9,29 @@ pub enum ColorFormat {
     RGBInteger,
     /// Parses the red, green, blue and alpha values as integers ranging from (0, 0, 0, 0) to (255, 255, 255, 255)
     RGBAInteger,
+    /// Parses three or four floating point values ranging from 0.0 to 1.0, chosen independently for every element.
+    /// Colors with three values are opaque.
+    Float,
+    /// Parses three or four integers ranging from 0 to 255, chosen independently for every element.
+    /// Colors with three values are opaque.
+    Integer,
+    /// Detects one of the other formats by inspecting all color values of the `off` string before parsing.
+    ///
+    /// Values are parsed as integers if none of them contains a decimal point or exponent and at least one is
+    /// greater than 1. If all colored elements have the same number of values, a format with that channel count is
+    /// chosen, otherwise [`ColorFormat::Float`] or [`ColorFormat::Integer`]. Colormap indices are ignored.
+    /// Without any colors and outside of parsing (e.g. when writing), this behaves like [`ColorFormat::RGBAFloat`].
+    Auto,
 }
 
 impl ColorFormat {
     /// Returns whether the color format is a floating point format.
     #[must_use]
     pub fn is_float(&self) -> bool {
-        matches!(self, ColorFormat::RGBFloat | ColorFormat::RGBAFloat)
+        matches!(
+            self,
+            ColorFormat::RGBFloat | ColorFormat::RGBAFloat | ColorFormat::Float | ColorFormat::Auto
+        )
     }
 
     /// Returns whether the color format is an integer format.
@@ -25,12 +41,28 @@ impl ColorFormat {
     }
 
     /// Returns whether the color format contains an alpha channel.
+    ///
+    /// For formats with a variable channel count this is whether the alpha channel may be present.
     #[must_use]
     pub fn has_alpha(&self) -> bool {
-        matches!(self, ColorFormat::RGBAFloat | ColorFormat::RGBAInteger)
+        !matches!(self, ColorFormat::RGBFloat | ColorFormat::RGBInteger)
+    }
+
+    /// Returns whether the channel count may differ between elements.
+    #[must_use]
+    pub fn is_variable(&self) -> bool {
+        matches!(self, ColorFormat::Float | ColorFormat::Integer)
+    }
+
+    /// Returns whether the color format is detected while parsing.
+    #[must_use]
+    pub fn is_auto(&self) -> bool {
+        matches!(self, ColorFormat::Auto)
     }
 
     /// Returns the number of channels in the color format.
+    ///
+    /// For formats with a variable channel count this is the maximum number of channels.
     #[must_use]
     pub fn channel_count(&self) -> usize {
         if self.has_alpha() {
@@ -39,13 +71,78 @@ impl ColorFormat {
             3
         }
     }
+
+    /// Returns whether a color consisting of `count` values can be parsed with the color format.
+    #[must_use]
+    pub fn accepts_channel_count(&self, count: usize) -> bool {
+        if self.is_variable() {
+            (3..=4).contains(&count)
+        } else {
+            count == self.channel_count()
+        }
+    }
 }
 
 impl Default for ColorFormat {
-    /// The default color format is [`RGBFloat`].
-    // Because this format is specified in the implementation of the Princeton Shape Benchmark.
+    /// The default color format is [`ColorFormat::Float`], which accepts three or four values for every color.
+    // Because floating point values are used by the implementation of the Princeton Shape Benchmark, while the spec
+    // allows the alpha channel to be omitted for every element.
     fn default() -> Self {
-        ColorFormat::RGBAFloat
+        ColorFormat::Float
+    }
+}
+
+/// Collects the color values of an `off` string to detect its [`ColorFormat`].
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Detector {
+    channel_count: Option<usize>,
+    variable: bool,
+    has_float: bool,
+    exceeds_one: bool,
+}
+
+impl Detector {
+    /// Inspects the color values of a single vertex or face.
+    ///
+    /// A single value is a colormap index and does not contribute to the detection.
+    pub fn add(&mut self, parts: &[&str]) {
+        if parts.len() < 2 {
+            return;
+        }
+
+        if (3..=4).contains(&parts.len()) {
+            match self.channel_count {
+                None => self.channel_count = Some(parts.len()),
+                Some(count) if count != parts.len() => self.variable = true,
+                Some(_) => {}
+            }
+        }
+
+        for part in parts {
+            if part.contains(|c: char| !c.is_ascii_digit()) {
+                self.has_float = true;
+            } else if part.parse::<u64>().map_or(true, |value| value > 1) {
+                self.exceeds_one = true;
+            }
+        }
+    }
+
+    /// Returns the detected [`ColorFormat`] or [`ColorFormat::RGBAFloat`] if no colors were inspected.
+    pub fn finish(self) -> ColorFormat {
+        let Some(channel_count) = self.channel_count else {
+            return ColorFormat::RGBAFloat;
+        };
+
+        let integer = !self.has_float && self.exceeds_one;
+
+        match (integer, channel_count) {
+            (true, _) if self.variable => ColorFormat::Integer,
+            (false, _) if self.variable => ColorFormat::Float,
+            (true, 3) => ColorFormat::RGBInteger,
+            (true, _) => ColorFormat::RGBAInteger,
+            (false, 3) => ColorFormat::RGBFloat,
+            (false, _) => ColorFormat::RGBAFloat,
+        }
     }
 }
 
@@ -74,5 +171,57 @@ mod tests {
         assert_eq!(ColorFormat::RGBAFloat.channel_count(), 4);
         assert_eq!(ColorFormat::RGBInteger.channel_count(), 3);
         assert_eq!(ColorFormat::RGBAInteger.channel_count(), 4);
+
+        assert!(ColorFormat::Float.is_float());
+        assert!(ColorFormat::Integer.is_integer());
+        assert!(ColorFormat::Float.is_variable());
+        assert!(ColorFormat::Integer.is_variable());
+        assert!(!ColorFormat::RGBAInteger.is_variable());
+        assert!(ColorFormat::Float.accepts_channel_count(3));
+        assert!(ColorFormat::Integer.accepts_channel_count(4));
+        assert!(!ColorFormat::Integer.accepts_channel_count(5));
+        assert!(ColorFormat::RGBFloat.accepts_channel_count(3));
+        assert!(!ColorFormat::RGBFloat.accepts_channel_count(4));
+
+        assert!(ColorFormat::Auto.is_auto());
+        assert!(!ColorFormat::RGBAFloat.is_auto());
+    }
+
+    fn detect(colors: &[&[&str]]) -> ColorFormat {
+        let mut detector = Detector::default();
+        for parts in colors {
+            detector.add(parts);
+        }
+        detector.finish()
+    }
+
+    #[test]
+    fn detector() {
+        assert_eq!(detect(&[]), ColorFormat::RGBAFloat);
+        assert_eq!(detect(&[&[], &[]]), ColorFormat::RGBAFloat);
+        assert_eq!(detect(&[&["255", "0", "12"]]), ColorFormat::RGBInteger);
+        assert_eq!(
+            detect(&[&["255", "0", "12", "255"]]),
+            ColorFormat::RGBAInteger
+        );
+        assert_eq!(detect(&[&["1", "0", "0.5"]]), ColorFormat::RGBFloat);
+        assert_eq!(detect(&[&["1", "0", "1e-1", "1"]]), ColorFormat::RGBAFloat);
+        assert_eq!(detect(&[&["1", "0", "0"]]), ColorFormat::RGBFloat);
+        assert_eq!(
+            detect(&[&[], &["1", "0", "0", "1"], &["0.5", "0", "0"]]),
+            ColorFormat::Float
+        );
+        assert_eq!(
+            detect(&[&["255", "0", "0"], &["255", "0", "0", "128"]]),
+            ColorFormat::Integer
+        );
+        assert_eq!(
+            detect(&[&["1", "0", "0"], &["128", "0", "0"]]),
+            ColorFormat::RGBInteger
+        );
+        assert_eq!(
+            detect(&[&["200"], &["0.5", "0", "0"]]),
+            ColorFormat::RGBFloat
+        );
     }
 }
@@ -1,17 +1,36 @@
 use std::{
     borrow::Cow,
     fmt::{Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+    num::{ParseFloatError, ParseIntError},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
 };
 
-/// An error that occured while parsing the `off` string line by line.
+use super::options::Limit;
+use crate::geometry::color;
+
+/// An error that occurred while parsing the `off` string line by line.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Error {
     /// The [`Kind`] of the error.
     pub kind: Kind,
-    /// The line number in the `off` string where the error occured.
+    /// The line number in the `off` string where the error occurred.
     pub line_index: usize,
     /// An error message describing the problem.
     pub message: Option<Cow<'static, str>>,
+    /// Structured information about the problem, which can be inspected instead of the message.
+    pub details: Details,
+    /// The underlying error and the path of the parsed file, which are rarely present and boxed to keep the
+    /// [`Error`] small.
+    context: Option<Box<Context>>,
+}
+
+/// The optional context of an [`Error`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+struct Context {
+    cause: Option<Cause>,
+    path: Option<PathBuf>,
 }
 
 impl Error {
@@ -22,6 +41,8 @@ impl Error {
             kind,
             line_index,
             message,
+            details: Details::None,
+            context: None,
         }
     }
 
@@ -40,20 +61,79 @@ impl Error {
     pub(crate) fn without_message(kind: Kind, line_index: usize) -> Self {
         Self::new(kind, line_index, None)
     }
+
+    /// Creates a new [`Kind::LimitExceeded`] error with [`Details::LimitExceeded`].
+    #[must_use]
+    pub(crate) fn limit_exceeded(
+        line_index: usize,
+        limit: Limit,
+        max: usize,
+        actual: usize,
+        message: &'static str,
+    ) -> Self {
+        Self::with_message(
+            Kind::LimitExceeded,
+            line_index,
+            format!("{message} (limit: {max}, actual: {actual})"),
+        )
+        .with_details(Details::LimitExceeded { limit, max, actual })
+    }
+
+    /// Attaches the given [`Details`] to the [`Error`].
+    #[must_use]
+    pub(crate) fn with_details(mut self, details: Details) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// Attaches the underlying error to the [`Error`].
+    #[must_use]
+    pub(crate) fn with_cause<C: Into<Cause>>(mut self, cause: C) -> Self {
+        self.context.get_or_insert_with(Box::default).cause = Some(cause.into());
+        self
+    }
+
+    /// Attaches the path of the parsed file to the [`Error`].
+    #[must_use]
+    pub(crate) fn with_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.context.get_or_insert_with(Box::default).path = Some(path.into());
+        self
+    }
+
+    /// Returns the underlying error, which is also returned by [`std::error::Error::source`].
+    #[must_use]
+    pub fn cause(&self) -> Option<&Cause> {
+        self.context.as_ref()?.cause.as_ref()
+    }
+
+    /// Returns the path of the parsed file, if the `off` string was read from a file.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.context.as_ref()?.path.as_deref()
+    }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause().map(Cause::as_error)
+    }
+}
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        if let Some(path) = self.path() {
+            write!(f, "{}: ", path.display())?;
+        }
+
         if let Some(msg) = &self.message {
             write!(f, "{} @ ln:{} - {}", self.kind, self.line_index + 1, msg)
         } else {
-            write!(f, "{} @ ln:{}", self.kind, self.line_index + 1,)
+            write!(f, "{} @ ln:{}", self.kind, self.line_index + 1)
         }
     }
 }
 
+/// The category of an [`Error`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Kind {
     /// The `off` string is empty.
@@ -62,7 +142,7 @@ pub enum Kind {
     Missing,
     /// A limit was exceeded.
     LimitExceeded,
-    /// The header has a invalid format.
+    /// The header has an invalid format.
     InvalidHeader,
     /// The counts of vertices, faces and edges have an invalid format.
     InvalidCounts,
@@ -70,10 +150,13 @@ pub enum Kind {
     InvalidVertexPosition,
     /// The color has an invalid format.
     InvalidColor,
-    /// The face definition has an invalid format.
+    /// The face definition has an invalid format (e.g. an invalid or mismatching number of vertex indices).
     InvalidFace,
-    /// The face indicies have an invalid format.
+    /// A face vertex index is not a valid number.
     InvalidFaceIndex,
+    /// A face vertex index or the total number of face vertex indices does not fit into the index type of a
+    /// [`CompactMesh`](crate::geometry::compact::CompactMesh).
+    IndexOverflow,
 }
 
 impl Display for Kind {
@@ -81,3 +164,148 @@ impl Display for Kind {
         Debug::fmt(self, f)
     }
 }
+
+/// Structured information about an [`Error`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Details {
+    /// No further information is available.
+    #[default]
+    None,
+    /// The number of elements is not in the expected range.
+    UnexpectedCount {
+        /// The counted [`Element`].
+        element: Element,
+        /// The accepted number of elements (`usize::MAX` as upper bound if there is none).
+        expected: RangeInclusive<usize>,
+        /// The number of elements found.
+        actual: usize,
+    },
+    /// A value exceeds one of the [`Limits`](`super::options::Limits`).
+    LimitExceeded {
+        /// The exceeded [`Limit`].
+        limit: Limit,
+        /// The maximum value allowed by the limit.
+        max: usize,
+        /// The value found.
+        ///
+        /// For [`Limit::ByteCount`] of streamed or compressed data this is a lower bound (`max + 1`), because reading
+        /// stops as soon as the limit is exceeded.
+        actual: usize,
+    },
+    /// A token could not be parsed as the given [`Element`].
+    InvalidToken {
+        /// The [`Element`] the token was parsed as.
+        element: Element,
+        /// The offending token.
+        token: String,
+    },
+}
+
+impl Details {
+    /// Creates [`Details::UnexpectedCount`] for an exact number of expected elements.
+    pub(crate) fn exact_count(element: Element, expected: usize, actual: usize) -> Self {
+        Self::UnexpectedCount {
+            element,
+            expected: expected..=expected,
+            actual,
+        }
+    }
+
+    /// Creates [`Details::UnexpectedCount`] for a minimum number of expected elements.
+    pub(crate) fn min_count(element: Element, min: usize, actual: usize) -> Self {
+        Self::UnexpectedCount {
+            element,
+            expected: min..=usize::MAX,
+            actual,
+        }
+    }
+
+    /// Creates [`Details::InvalidToken`] for the given token.
+    pub(crate) fn invalid_token(element: Element, token: &str) -> Self {
+        Self::InvalidToken {
+            element,
+            token: token.to_string(),
+        }
+    }
+}
+
+/// The parts of an `off` string that [`Details`] refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Element {
+    /// The header line (`OFF`).
+    Header,
+    /// A value of the counts line.
+    Count,
+    /// A vertex line.
+    Vertex,
+    /// A face line.
+    Face,
+    /// A coordinate of a vertex position.
+    Coordinate,
+    /// A channel of a vertex or face color.
+    ColorChannel,
+    /// A colormap index of a face.
+    ColorIndex,
+    /// The number of vertices at the start of a face line.
+    FaceVertexCount,
+    /// A vertex index of a face.
+    FaceVertex,
+}
+
+/// The underlying error of an [`Error`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Cause {
+    /// A token could not be parsed as a floating point number.
+    ParseFloat(ParseFloatError),
+    /// A token could not be parsed as an integer.
+    ParseInt(ParseIntError),
+    /// The parsed values do not form a valid color.
+    Color(color::Error),
+}
+
+impl Cause {
+    /// Returns the underlying error as trait object.
+    #[must_use]
+    pub fn as_error(&self) -> &(dyn std::error::Error + 'static) {
+        match self {
+            Cause::ParseFloat(err) => err,
+            Cause::ParseInt(err) => err,
+            Cause::Color(err) => err,
+        }
+    }
+}
+
+impl Hash for Cause {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The standard library errors do not implement `Hash`, but equal errors have equal messages
+        std::mem::discriminant(self).hash(state);
+        self.as_error().to_string().hash(state);
+    }
+}
+
+impl Display for Cause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        Display::fmt(self.as_error(), f)
+    }
+}
+
+impl From<ParseFloatError> for Cause {
+    fn from(err: ParseFloatError) -> Self {
+        Cause::ParseFloat(err)
+    }
+}
+
+impl From<ParseIntError> for Cause {
+    fn from(err: ParseIntError) -> Self {
+        Cause::ParseInt(err)
+    }
+}
+
+impl From<color::Error> for Cause {
+    fn from(err: color::Error) -> Self {
+        Cause::Color(err)
+    }
+}
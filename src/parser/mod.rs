@@ -2,23 +2,43 @@ pub mod color_format;
 pub mod error;
 mod iter;
 pub mod options;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod utils;
 
+#[cfg(fuzzing)]
+#[doc(hidden)]
+pub use utils::check_parity;
+#[doc(hidden)]
+pub use utils::parse_floats;
+
 use crate::geometry::{
     color::Color,
-    mesh::{Face, Mesh, Vertex},
+    colormap::Colormap,
+    compact::CompactMesh,
+    mesh::{Face, FaceIndices, Mesh, Vertex},
     position::Position,
 };
 
 use self::{
-    error::{Error, Kind},
+    color_format::{ColorFormat, Detector},
+    error::{Details, Element, Error, Kind},
     iter::OffLines,
-    options::Options,
-    utils::{ConvertVec, StrParts},
+    options::{Limit, Options},
+    utils::{ConvertVec, FastParse, StrParts},
 };
 
 pub type Result<T = ()> = std::result::Result<T, Error>;
 
+/// The minimum length of a vertex line in bytes including the line break (e.g. `0 0 0\n`).
+const MIN_VERTEX_LINE_LEN: usize = 6;
+
+/// The minimum length of a face line in bytes including the line break (e.g. `3 0 1 2\n`).
+const MIN_FACE_LINE_LEN: usize = 8;
+
+/// The minimum length of a face vertex index in bytes including the separator (e.g. `0 `).
+const MIN_FACE_INDEX_LEN: usize = 2;
+
 /// Parses a [`crate::geometry::mesh::Mesh`] from a `off` string.
 #[derive(Debug, Clone)]
 pub struct Parser<'a> {
@@ -29,12 +49,15 @@ pub struct Parser<'a> {
     edge_count: usize,
     document: Mesh,
     options: Options,
+    color_format: ColorFormat,
+    byte_count: usize,
 }
 
 impl<'a> Parser<'a> {
     /// Creates a new [`Parser`] from a `off` string.
     pub fn new<S: AsRef<str>>(s: &'a S, options: Options) -> Self {
-        let lines = OffLines::new(s.as_ref());
+        let s = s.as_ref();
+        let lines = OffLines::new(s);
 
         Parser {
             lines,
@@ -44,6 +67,8 @@ impl<'a> Parser<'a> {
             edge_count: 0,
             document: Mesh::new(),
             options,
+            color_format: options.color_format,
+            byte_count: s.len(),
         }
     }
 
@@ -52,13 +77,71 @@ impl<'a> Parser<'a> {
     /// # Errors
     ///
     /// Will return `Error` if an error occurs while parsing the `off` data.
-    pub fn parse(mut self) -> crate::Result {
+    pub fn parse(self) -> crate::Result {
+        let (mesh, _) = self.parse_with_color_format()?;
+
+        Ok(mesh)
+    }
+
+    /// Parses the `off` string and returns the [`crate::geometry::mesh::Mesh`] together with the [`ColorFormat`]
+    /// that was used to parse the colors.
+    ///
+    /// This is the detected format if [`ColorFormat::Auto`] is configured and the configured format otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error` if an error occurs while parsing the `off` data.
+    pub fn parse_with_color_format(mut self) -> crate::Result<(Mesh, ColorFormat)> {
+        self.check_byte_count()?;
         self.parse_header()?;
         self.parse_counts()?;
+
+        if self.color_format.is_auto() {
+            self.color_format = self.detect_color_format();
+        }
+
         self.parse_vertices()?;
         self.parse_faces()?;
 
-        Ok(self.finalize())
+        let color_format = self.color_format;
+
+        Ok((self.finalize(), color_format))
+    }
+
+    /// Parses the `off` string directly into a [`CompactMesh`] without creating a [`Mesh`] first.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error` if an error occurs while parsing the `off` data or if a vertex index or the total number of
+    /// face indices does not fit into an [`u32`].
+    pub fn parse_compact(mut self) -> crate::Result<CompactMesh> {
+        self.check_byte_count()?;
+        self.parse_header()?;
+        self.parse_counts()?;
+
+        if self.color_format.is_auto() {
+            self.color_format = self.detect_color_format();
+        }
+
+        let mut mesh = CompactMesh::new();
+        mesh.reserve(
+            self.capacity(self.vertex_count, MIN_VERTEX_LINE_LEN),
+            self.capacity(self.face_count, MIN_FACE_LINE_LEN),
+            self.index_capacity(),
+        );
+
+        self.parse_vertices_into(|vertex| mesh.push_vertex(vertex))?;
+
+        // The buffer only holds the indices of the current face
+        let mut indices = Vec::new();
+        self.parse_faces_into(&mut indices, |line_index, face, indices| {
+            let result = mesh.push_face(indices, face.color, face.color_index);
+            indices.clear();
+
+            result.map_err(|message| Error::with_message(Kind::IndexOverflow, line_index, message))
+        })?;
+
+        Ok(mesh)
     }
 
     /// Progress to the next line.
@@ -76,12 +159,20 @@ impl<'a> Parser<'a> {
             .next_line()
             .ok_or_else(|| Error::without_message(Kind::Empty, 0))?;
 
+        self.check_limit(
+            line_index,
+            Limit::LineLength,
+            line.len(),
+            "Line length exceeds limit",
+        )?;
+
         if line != "OFF" {
             return Err(Error::with_message(
                 Kind::InvalidHeader,
                 line_index,
                 "First non-comment line should be `OFF`",
-            ));
+            )
+            .with_details(Details::invalid_token(Element::Header, line)));
         }
 
         Ok(())
@@ -93,14 +184,22 @@ impl<'a> Parser<'a> {
             Error::with_message(Kind::Missing, self.prev_line_index + 1, "No counts present")
         })?;
 
-        let counts: Vec<&str> = line.split_line();
+        let mut counts = Vec::new();
+        self.tokenize(line_index, line, &mut counts)?;
+
+        let num: Vec<usize> = counts.clone().parse_string_to().map_err(|err| {
+            let token = counts
+                .iter()
+                .find(|count| count.parse::<usize>().is_err())
+                .unwrap_or(&line);
 
-        let num: Vec<usize> = counts.parse_string_to().map_err(|err| {
             Error::with_message(
                 Kind::InvalidCounts,
                 line_index,
-                format!("Failed to parse count as number ({})", err),
+                format!("Failed to parse count as number ({err})"),
             )
+            .with_details(Details::invalid_token(Element::Count, token))
+            .with_cause(err)
         })?;
 
         match num[..] {
@@ -121,57 +220,177 @@ impl<'a> Parser<'a> {
                         "Invalid amount of counts present (expected: 2-3, actual: {})",
                         num.len()
                     ),
-                ));
+                )
+                .with_details(Details::UnexpectedCount {
+                    element: Element::Count,
+                    expected: 2..=3,
+                    actual: num.len(),
+                }));
             }
         }
 
         // Check for limits
-        if self.vertex_count > self.options.limits.vertex_count {
-            return Err(Error::with_message(
-                Kind::LimitExceeded,
-                line_index,
-                format!(
-                    "Vertext count exceeds limit (limit: {}, actual: {})",
-                    self.options.limits.vertex_count, self.vertex_count
-                ),
+        self.check_limit(
+            line_index,
+            Limit::VertexCount,
+            self.vertex_count,
+            "Vertex count exceeds limit",
+        )?;
+        self.check_limit(
+            line_index,
+            Limit::FaceCount,
+            self.face_count,
+            "Face count exceeds limit",
+        )?;
+
+        Ok(())
+    }
+
+    /// Returns a [`Kind::LimitExceeded`] error if `actual` exceeds the given [`Limit`].
+    fn check_limit(
+        &self,
+        line_index: usize,
+        limit: Limit,
+        actual: usize,
+        message: &'static str,
+    ) -> Result {
+        let max = self.options.limits.get(limit);
+
+        if actual > max {
+            return Err(Error::limit_exceeded(
+                line_index, limit, max, actual, message,
             ));
         }
 
-        if self.face_count > self.options.limits.face_count {
-            return Err(Error::with_message(
-                Kind::LimitExceeded,
+        Ok(())
+    }
+
+    /// Returns a [`Kind::LimitExceeded`] error if the `off` string exceeds [`Limits::byte_count`](`options::Limits`).
+    fn check_byte_count(&self) -> Result {
+        self.check_limit(
+            0,
+            Limit::ByteCount,
+            self.byte_count,
+            "Input size exceeds limit",
+        )
+    }
+
+    /// Splits the line into `parts` after checking the line length and token count limits.
+    ///
+    /// At most one token more than the limit is collected, so a line with lots of tokens does not cause a large
+    /// allocation.
+    fn tokenize<'l>(&self, line_index: usize, line: &'l str, parts: &mut Vec<&'l str>) -> Result {
+        self.check_limit(
+            line_index,
+            Limit::LineLength,
+            line.len(),
+            "Line length exceeds limit",
+        )?;
+
+        let max = self.options.limits.token_count;
+
+        parts.clear();
+        parts.extend(line.tokens().take(max.saturating_add(1)));
+
+        if parts.len() > max {
+            self.check_limit(
                 line_index,
-                format!(
-                    "Face count exceeds limit (limit: {}, actual: {})",
-                    self.options.limits.face_count, self.face_count
-                ),
-            ));
+                Limit::TokenCount,
+                line.tokens().count(),
+                "Token count of line exceeds limit",
+            )?;
         }
 
         Ok(())
     }
 
+    /// Returns whether the line would be rejected by [`Parser::tokenize`] because it exceeds a limit.
+    fn exceeds_line_limits(&self, line: &str) -> bool {
+        line.len() > self.options.limits.line_length
+            || line.tokens().nth(self.options.limits.token_count).is_some()
+    }
+
+    /// Detects the [`ColorFormat`] by inspecting the colors of all vertices and faces without consuming them.
+    fn detect_color_format(&self) -> ColorFormat {
+        let mut detector = Detector::default();
+        let mut lines = self.lines.clone();
+        let mut parts = Vec::new();
+
+        // Lines exceeding a limit are skipped, the error is returned when they are parsed
+        for (_, line) in lines.by_ref().take(self.vertex_count) {
+            if self.exceeds_line_limits(line) {
+                continue;
+            }
+
+            parts.clear();
+            parts.extend(line.tokens());
+            detector.add(parts.get(3..).unwrap_or_default());
+        }
+
+        for (_, line) in lines.take(self.face_count) {
+            if self.exceeds_line_limits(line) {
+                continue;
+            }
+
+            parts.clear();
+            parts.extend(line.tokens());
+            let colors = parts
+                .first()
+                .and_then(|count| usize::parse_fast(count).ok())
+                .and_then(|count| parts.get(count.checked_add(1)?..));
+            detector.add(colors.unwrap_or_default());
+        }
+
+        detector.finish()
+    }
+
+    /// Returns the capacity to reserve for `count` elements whose definitions are at least `min_len` bytes long.
+    ///
+    /// The capacity is bounded by the number of elements the `off` string can actually contain, so counts exceeding
+    /// the data (e.g. with [`Limits::MAX`](`options::Limits`)) do not cause a large allocation.
+    fn capacity(&self, count: usize, min_len: usize) -> usize {
+        count.min(self.byte_count / min_len)
+    }
+
     /// Parses the vertices of the `off` string.
     fn parse_vertices(&mut self) -> Result {
-        for _ in 0..self.vertex_count {
+        let mut vertices = std::mem::take(&mut self.document.vertices);
+        vertices.reserve_exact(self.capacity(self.vertex_count, MIN_VERTEX_LINE_LEN));
+
+        let result = self.parse_vertices_into(|vertex| vertices.push(vertex));
+        self.document.vertices = vertices;
+
+        result
+    }
+
+    /// Parses the vertices of the `off` string and passes them to `push`.
+    fn parse_vertices_into<F: FnMut(Vertex)>(&mut self, mut push: F) -> Result {
+        // The buffer is reused for all lines to avoid an allocation per line
+        let mut parts = Vec::new();
+
+        for index in 0..self.vertex_count {
             let (line_index, line) = self.next_line().ok_or_else(|| {
                 Error::with_message(
                     Kind::Missing,
                     self.prev_line_index + 1,
                     "Expected vertex definition",
                 )
+                .with_details(Details::exact_count(
+                    Element::Vertex,
+                    self.vertex_count,
+                    index,
+                ))
             })?;
 
-            let parts = line.split_line();
-            let vertex = self.parse_vertex(line_index, &parts)?;
-            self.document.vertices.push(vertex);
+            self.tokenize(line_index, line, &mut parts)?;
+            push(self.parse_vertex(line_index, &parts)?);
         }
 
         Ok(())
     }
 
     /// Parses a vertex from a `off` string.
-    fn parse_vertex(&mut self, line_index: usize, parts: &[&str]) -> Result<Vertex> {
+    fn parse_vertex(&self, line_index: usize, parts: &[&str]) -> Result<Vertex> {
         if parts.len() < 3 {
             return Err(Error::with_message(
                 Kind::InvalidVertexPosition,
@@ -180,7 +399,8 @@ impl<'a> Parser<'a> {
                     "Not enough parts for position (expected: >= 3, actual: {})",
                     parts.len()
                 ),
-            ));
+            )
+            .with_details(Details::exact_count(Element::Coordinate, 3, parts.len())));
         }
 
         let position = Parser::parse_position(line_index, &parts[0..=2])?;
@@ -204,150 +424,238 @@ impl<'a> Parser<'a> {
                     "Invalid number of coordinates given (expected: 3, actual: {})",
                     parts.len()
                 ),
-            ));
+            )
+            .with_details(Details::exact_count(Element::Coordinate, 3, parts.len())));
         }
 
-        let position_parts: Vec<f32> = parts
-            .iter()
-            .map(|s| {
-                s.parse().map_err(|err| {
-                    Error::with_message(
-                        Kind::InvalidVertexPosition,
-                        line_index,
-                        format!("Failed to parse coordinate as number: ({})", err),
-                    )
-                })
-            })
-            .collect::<Result<Vec<f32>>>()?;
+        let mut coordinates = [0.0; 3];
+        for (coordinate, s) in coordinates.iter_mut().zip(parts) {
+            *coordinate = f32::parse_fast(s).map_err(|err| {
+                Error::with_message(
+                    Kind::InvalidVertexPosition,
+                    line_index,
+                    format!("Failed to parse coordinate as number: ({err})"),
+                )
+                .with_details(Details::invalid_token(Element::Coordinate, s))
+                .with_cause(err)
+            })?;
+        }
 
-        Position::try_from(position_parts).map_err(|err| {
-            Error::with_message(
-                Kind::InvalidVertexPosition,
-                line_index,
-                format!("Failed to parse position: ({})", err),
-            )
-        })
+        let [x, y, z] = coordinates;
+        Ok(Position::new(x, y, z))
     }
 
     /// Parses a color from a `off` string.
-    fn parse_color(&mut self, line_index: usize, parts: &[&str]) -> Result<Color> {
-        if parts.len() != self.options.color_format.channel_count() {
+    fn parse_color(&self, line_index: usize, parts: &[&str]) -> Result<Color> {
+        if !self.color_format.accepts_channel_count(parts.len()) {
+            let (expected, range) = if self.color_format.is_variable() {
+                ("3-4".to_string(), 3..=4)
+            } else {
+                let count = self.color_format.channel_count();
+                (count.to_string(), count..=count)
+            };
+
             return Err(Error::with_message(
                 Kind::InvalidColor,
                 line_index,
                 format!(
-                    "Invalid number of color elements given (expected: {}, actual: {})",
-                    self.options.color_format.channel_count(),
+                    "Invalid number of color elements given (expected: {expected}, actual: {})",
                     parts.len()
                 ),
-            ));
+            )
+            .with_details(Details::UnexpectedCount {
+                element: Element::ColorChannel,
+                expected: range,
+                actual: parts.len(),
+            }));
         }
 
-        if self.options.color_format.is_float() {
+        if self.color_format.is_float() {
             // parse as f32
-            let color_parts = parts
-                .iter()
-                .map(|s| {
-                    s.parse::<f32>().map_err(|err| {
-                        Error::with_message(
-                            Kind::InvalidColor,
-                            line_index,
-                            format!("Failed to parse color as float: {}", err),
-                        )
-                    })
-                })
-                .collect::<Result<Vec<f32>>>()?;
+            let mut color_parts = [0.0; 4];
+            for (channel, s) in color_parts.iter_mut().zip(parts) {
+                *channel = f32::parse_fast(s).map_err(|err| {
+                    Error::with_message(
+                        Kind::InvalidColor,
+                        line_index,
+                        format!("Failed to parse color as float: {err}"),
+                    )
+                    .with_details(Details::invalid_token(Element::ColorChannel, s))
+                    .with_cause(err)
+                })?;
+            }
+
+            let color_parts = &color_parts[..parts.len()];
 
             Color::try_from(color_parts).map_err(|err| {
+                let details = color_parts
+                    .iter()
+                    .position(|channel| !(0.0..=1.0).contains(channel))
+                    .map_or(Details::None, |index| {
+                        Details::invalid_token(Element::ColorChannel, parts[index])
+                    });
+
                 Error::with_message(
                     Kind::InvalidColor,
                     line_index,
-                    format!("Failed to parse color: {}", err),
+                    format!("Failed to parse color: {err}"),
                 )
+                .with_details(details)
+                .with_cause(err)
             })
         } else {
             // parse as u8
-            let color_parts = parts
-                .iter()
-                .map(|s| {
-                    s.parse::<u8>().map_err(|err| {
-                        Error::with_message(
-                            Kind::InvalidColor,
-                            line_index,
-                            format!("Failed to parse color as u8: {}", err),
-                        )
-                    })
-                })
-                .collect::<Result<Vec<u8>>>()?;
+            let mut color_parts = [0; 4];
+            for (channel, s) in color_parts.iter_mut().zip(parts) {
+                *channel = u8::parse_fast(s).map_err(|err| {
+                    Error::with_message(
+                        Kind::InvalidColor,
+                        line_index,
+                        format!("Failed to parse color as u8: {err}"),
+                    )
+                    .with_details(Details::invalid_token(Element::ColorChannel, s))
+                    .with_cause(err)
+                })?;
+            }
 
-            Color::try_from(color_parts).map_err(|err| {
+            Color::try_from(&color_parts[..parts.len()]).map_err(|err| {
                 Error::with_message(
                     Kind::InvalidColor,
                     line_index,
-                    format!("Failed to parse color: {}", err),
+                    format!("Failed to parse color: {err}"),
                 )
+                .with_cause(err)
             })
         }
     }
 
     /// Parses the faces of the `off` string.
     fn parse_faces(&mut self) -> Result {
-        for _ in 0..self.face_count {
+        let mut faces = std::mem::take(&mut self.document.faces);
+        faces.reserve_exact(self.capacity(self.face_count, MIN_FACE_LINE_LEN));
+
+        // The indices of all faces are stored in a single buffer shared by the faces
+        let mut indices = Vec::with_capacity(self.index_capacity());
+
+        let result = self.parse_faces_into(&mut indices, |_, face, _| {
+            faces.push(face);
+            Ok(())
+        });
+        self.document.faces = faces;
+        self.attach_indices(indices);
+
+        result
+    }
+
+    /// Parses the faces of the `off` string and passes them to `push`.
+    ///
+    /// The indices of the faces are appended to `indices`, which is passed to `push` together with the line index and
+    /// the face whose [`FaceIndices`] are pending.
+    fn parse_faces_into<F>(&mut self, indices: &mut Vec<usize>, mut push: F) -> Result
+    where
+        F: FnMut(usize, Face, &mut Vec<usize>) -> Result,
+    {
+        // The buffer is reused for all lines to avoid an allocation per line
+        let mut parts = Vec::new();
+        let mut face_index_count = 0;
+
+        for index in 0..self.face_count {
             let (line_index, line) = self.next_line().ok_or_else(|| {
                 Error::with_message(
                     Kind::Missing,
                     self.prev_line_index + 1,
                     "Expected face definition",
                 )
+                .with_details(Details::exact_count(
+                    Element::Face,
+                    self.face_count,
+                    index,
+                ))
             })?;
 
-            let parts: Vec<&str> = line.split_line();
-            let face = self.parse_face(line_index, &parts)?;
-            self.document.faces.push(face);
+            self.tokenize(line_index, line, &mut parts)?;
+            let face = self.parse_face(line_index, &parts, indices)?;
+
+            face_index_count += face.vertices.range().len();
+            self.check_face_index_count(line_index, face_index_count)?;
+
+            push(line_index, face, indices)?;
         }
 
         Ok(())
     }
 
-    /// Parses a face from a `off` string.
-    fn parse_face(&mut self, line_index: usize, mut parts: &[&str]) -> Result<Face> {
+    /// Returns the capacity to reserve for the face vertex indices.
+    ///
+    /// Every face has at least three vertices, larger faces grow the buffer.
+    fn index_capacity(&self) -> usize {
+        self.capacity(self.face_count.saturating_mul(3), MIN_FACE_INDEX_LEN)
+            .min(self.options.limits.face_index_count)
+    }
+
+    /// Attaches the faces to the buffer of their vertex `indices`.
+    fn attach_indices(&mut self, indices: Vec<usize>) {
+        let faces = self.document.faces.iter_mut();
+        FaceIndices::attach(faces.map(|face| &mut face.vertices), indices);
+    }
+
+    /// Returns a [`Kind::LimitExceeded`] error if the total number of face indices exceeds the limit.
+    fn check_face_index_count(&self, line_index: usize, face_index_count: usize) -> Result {
+        self.check_limit(
+            line_index,
+            Limit::FaceIndexCount,
+            face_index_count,
+            "Total face index count exceeds limit",
+        )
+    }
+
+    /// Parses a face from a `off` string, appending its vertex indices to `indices`.
+    ///
+    /// The [`FaceIndices`] of the face are pending until they are attached to `indices`.
+    fn parse_face(
+        &self,
+        line_index: usize,
+        mut parts: &[&str],
+        indices: &mut Vec<usize>,
+    ) -> Result<Face> {
         if parts.len() < 4 {
             return Err(Error::with_message(
                 Kind::InvalidFace,
                 line_index,
-                format!("Not enough arguments. At least three vertex indicies required (e.g. `3 1 2 3`). {} arguments given", parts.len()),
-            ));
+                format!("Not enough arguments. At least three vertex indices required (e.g. `3 1 2 3`). {} arguments given", parts.len()),
+            )
+            .with_details(Details::min_count(
+                Element::FaceVertex,
+                3,
+                parts.len().saturating_sub(1),
+            )));
         }
 
-        let vertex_count: usize = parts[0].parse().map_err(|err| {
+        let vertex_count = usize::parse_fast(parts[0]).map_err(|err| {
             Error::with_message(
                 Kind::InvalidFace,
                 line_index,
-                format!("Failed to parse vertex count for face definition: {}", err),
+                format!("Failed to parse vertex count for face definition: {err}"),
             )
+            .with_details(Details::invalid_token(Element::FaceVertexCount, parts[0]))
+            .with_cause(err)
         })?;
 
         if vertex_count < 3 {
             return Err(Error::with_message(
                 Kind::InvalidFace,
                 line_index,
-                format!(
-                    "Vertex count should be at least 3 (actual: {})",
-                    vertex_count
-                ),
-            ));
+                format!("Vertex count should be at least 3 (actual: {vertex_count})"),
+            )
+            .with_details(Details::min_count(Element::FaceVertex, 3, vertex_count)));
         }
 
-        if vertex_count > self.options.limits.face_vertex_count {
-            return Err(Error::with_message(
-                Kind::LimitExceeded,
-                line_index,
-                format!(
-                    "Vertex count of face exceeds limit (limit: {}, actual: {})",
-                    self.options.limits.face_vertex_count, vertex_count
-                ),
-            ));
-        }
+        self.check_limit(
+            line_index,
+            Limit::FaceVertexCount,
+            vertex_count,
+            "Vertex count of face exceeds limit",
+        )?;
 
         // "Consume" vertex_count
         parts = &parts[1..];
@@ -361,7 +669,8 @@ impl<'a> Parser<'a> {
                     "Not enough vertices for face (expected: >= 3, actual: {})",
                     parts.len()
                 ),
-            ));
+            )
+            .with_details(Details::min_count(Element::FaceVertex, 3, parts.len())));
         }
 
         // sanity check: at least vertex_count parts
@@ -374,56 +683,72 @@ impl<'a> Parser<'a> {
                     vertex_count,
                     parts.len()
                 ),
-            ));
+            )
+            .with_details(Details::exact_count(
+                Element::FaceVertex,
+                vertex_count,
+                parts.len(),
+            )));
         }
 
-        let vertices = Parser::parse_face_indices(line_index, vertex_count, parts)?;
+        let start = indices.len();
+        Parser::parse_face_indices(line_index, vertex_count, parts, indices)?;
 
         // "Consume" vertex indexes
         parts = &parts[vertex_count..];
 
-        let color = if parts.is_empty() {
-            None
-        } else {
-            Some(self.parse_color(line_index, parts)?)
+        let (color, color_index) = match parts {
+            [] => (None, None),
+            [color_index] => {
+                let color_index = Parser::parse_color_index(line_index, color_index)?;
+                (Colormap::default_color(color_index), Some(color_index))
+            }
+            _ => (Some(self.parse_color(line_index, parts)?), None),
         };
 
-        Ok(Face { vertices, color })
+        Ok(Face {
+            vertices: FaceIndices::pending(start, indices.len()),
+            color,
+            color_index,
+        })
+    }
+
+    /// Parses a single colormap index, which can be given instead of the color values of a face.
+    fn parse_color_index(line_index: usize, part: &str) -> Result<usize> {
+        usize::parse_fast(part).map_err(|err| {
+            Error::with_message(
+                Kind::InvalidColor,
+                line_index,
+                format!("Failed to parse colormap index as integer: {err}"),
+            )
+            .with_details(Details::invalid_token(Element::ColorIndex, part))
+            .with_cause(err)
+        })
     }
 
-    /// Parses the face vertex indices from a line.
+    /// Parses the first `vertex_count` face vertex indices from a line and appends them to `indices`.
+    ///
+    /// The caller ensures that `parts` holds at least `vertex_count` indices.
     fn parse_face_indices(
         line_index: usize,
         vertex_count: usize,
         parts: &[&str],
-    ) -> Result<Vec<usize>> {
-        let vertices: Vec<usize> = parts
-            .iter()
-            .take(vertex_count)
-            .map(|s| {
-                s.parse().map_err(|err| {
-                    Error::with_message(
-                        Kind::InvalidFaceIndex,
-                        line_index,
-                        format!("Failed to parse vertex index as number: ({})", err),
-                    )
-                })
-            })
-            .collect::<Result<Vec<usize>>>()?;
-
-        if vertices.len() != vertex_count {
-            return Err(Error::with_message(
-                Kind::InvalidFaceIndex,
-                line_index,
-                format!(
-                    "Invalid number of face indexes given (expected: {}, actual: {})",
-                    vertex_count,
-                    vertices.len()
-                ),
-            ));
+        indices: &mut Vec<usize>,
+    ) -> Result {
+        for s in parts.iter().take(vertex_count) {
+            let index = usize::parse_fast(s).map_err(|err| {
+                Error::with_message(
+                    Kind::InvalidFaceIndex,
+                    line_index,
+                    format!("Failed to parse vertex index as number: ({err})"),
+                )
+                .with_details(Details::invalid_token(Element::FaceVertex, s))
+                .with_cause(err)
+            })?;
+            indices.push(index);
         }
 
-        Ok(vertices)
+        Ok(())
     }
 
     /// Finalizes the parsing by returning the [`Mesh`].
@@ -436,10 +761,21 @@ impl<'a> Parser<'a> {
 #[allow(unused)]
 mod tests {
 
-    use crate::parser::color_format::ColorFormat;
+    use crate::parser::{color_format::ColorFormat, options::Limits};
 
     use super::*;
 
+    impl Parser<'_> {
+        /// Parses a single face with its own index buffer.
+        fn parse_single_face(&self, line_index: usize, parts: &[&str]) -> Result<Face> {
+            let mut indices = Vec::new();
+            let mut face = self.parse_face(line_index, parts, &mut indices)?;
+            FaceIndices::attach([&mut face.vertices], indices);
+
+            Ok(face)
+        }
+    }
+
     #[test]
     fn parse_header() {
         let mut parser = Parser::new(&"OFF", Options::default());
@@ -525,6 +861,178 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parse_counts_limits_details() {
+        let mut parser = Parser::new(&"10 1001", Options::default());
+        assert_eq!(
+            parser.parse_counts().unwrap_err().details,
+            Details::LimitExceeded {
+                limit: Limit::FaceCount,
+                max: 1000,
+                actual: 1001,
+            }
+        );
+    }
+
+    fn limits_details(off: &str, limits: Limits) -> (usize, Details) {
+        let options = Options {
+            color_format: ColorFormat::Auto,
+            limits,
+        };
+        match Parser::new(&off, options).parse() {
+            Err(crate::Error::ParserError(error)) => (error.line_index, error.details),
+            result => panic!("expected parser error, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_byte_count_limit() {
+        let limits = Limits {
+            byte_count: 9,
+            ..Limits::default()
+        };
+        assert_eq!(
+            limits_details("OFF\n0 0 0\n", limits),
+            (
+                0,
+                Details::LimitExceeded {
+                    limit: Limit::ByteCount,
+                    max: 9,
+                    actual: 10,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn parse_line_length_limit() {
+        let limits = Limits {
+            line_length: 11,
+            ..Limits::default()
+        };
+        // Comments and surrounding whitespace do not count
+        assert!(Parser::new(
+            &"OFF\n1 0\n  1.0 2.0 3.0  # comment\n",
+            Options {
+                limits,
+                ..Options::default()
+            }
+        )
+        .parse()
+        .is_ok());
+        assert_eq!(
+            limits_details("OFF\n1 0\n1.0 2.0 3.00\n", limits),
+            (
+                2,
+                Details::LimitExceeded {
+                    limit: Limit::LineLength,
+                    max: 11,
+                    actual: 12,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn parse_token_count_limit() {
+        let limits = Limits {
+            token_count: 4,
+            ..Limits::default()
+        };
+        assert_eq!(
+            limits_details("OFF\n3 1\n0 0 0\n1 0 0\n0 1 0\n3 0 1 2 0 0 0\n", limits),
+            (
+                5,
+                Details::LimitExceeded {
+                    limit: Limit::TokenCount,
+                    max: 4,
+                    actual: 7,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn parse_face_index_count_limit() {
+        let limits = Limits {
+            face_index_count: 5,
+            ..Limits::default()
+        };
+        assert_eq!(
+            limits_details("OFF\n3 2\n0 0 0\n1 0 0\n0 1 0\n3 0 1 2\n3 2 1 0\n", limits),
+            (
+                6,
+                Details::LimitExceeded {
+                    limit: Limit::FaceIndexCount,
+                    max: 5,
+                    actual: 6,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn parse_faces_shared_buffer() {
+        let mut parser = Parser::new(&"3 0 1 2\n4 3 2 1 0\n", Options::default());
+        parser.face_count = 2;
+        parser.parse_faces().unwrap();
+
+        let faces = &parser.document.faces;
+        assert_eq!(faces[0].vertices, [0, 1, 2]);
+        assert_eq!(faces[1].vertices, [3, 2, 1, 0]);
+        assert!(std::ptr::eq(
+            faces[0].vertices.as_slice().as_ptr_range().end,
+            faces[1].vertices.as_slice().as_ptr()
+        ));
+    }
+
+    #[test]
+    fn capacity_bounded_by_input() {
+        let off = "OFF\n1000000000000 1000000000000\n0 0 0\n";
+        let options = Options {
+            limits: Limits::MAX,
+            ..Options::default()
+        };
+
+        let parser = Parser::new(&off, options);
+        assert_eq!(
+            parser.capacity(usize::MAX, MIN_VERTEX_LINE_LEN),
+            off.len() / 6
+        );
+
+        // Does not try to allocate the declared number of elements
+        assert!(matches!(
+            Parser::new(&off, options).parse(),
+            Err(crate::Error::ParserError(Error {
+                kind: Kind::Missing,
+                line_index: 3,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn parse_counts_details() {
+        let mut parser = Parser::new(&"3 x 0", Options::default());
+        assert_eq!(
+            parser.parse_counts().unwrap_err().details,
+            Details::InvalidToken {
+                element: Element::Count,
+                token: "x".to_string(),
+            }
+        );
+
+        let mut parser = Parser::new(&"3", Options::default());
+        assert_eq!(
+            parser.parse_counts().unwrap_err().details,
+            Details::UnexpectedCount {
+                element: Element::Count,
+                expected: 2..=3,
+                actual: 1,
+            }
+        );
+    }
+
     #[test]
     fn parse_vertices() {
         let mut parser = Parser::new(
@@ -760,6 +1268,54 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parse_face_details() {
+        let parser = Parser::new(&"", Options::default());
+
+        assert_eq!(
+            parser
+                .parse_single_face(0, &["5", "1", "2", "3"])
+                .unwrap_err()
+                .details,
+            Details::UnexpectedCount {
+                element: Element::FaceVertex,
+                expected: 5..=5,
+                actual: 3,
+            }
+        );
+        assert_eq!(
+            parser
+                .parse_single_face(0, &["65", "1", "2", "3"])
+                .unwrap_err()
+                .details,
+            Details::LimitExceeded {
+                limit: Limit::FaceVertexCount,
+                max: 64,
+                actual: 65,
+            }
+        );
+        assert_eq!(
+            parser
+                .parse_single_face(0, &["3", "1", "-2", "3"])
+                .unwrap_err()
+                .details,
+            Details::InvalidToken {
+                element: Element::FaceVertex,
+                token: "-2".to_string(),
+            }
+        );
+        assert_eq!(
+            parser
+                .parse_single_face(0, &["3", "1", "2", "3", "0.5", "1.5", "0", "1"])
+                .unwrap_err()
+                .details,
+            Details::InvalidToken {
+                element: Element::ColorChannel,
+                token: "1.5".to_string(),
+            }
+        );
+    }
+
     #[test]
     fn parse_faces() {
         let mut parser = Parser::new(
@@ -796,13 +1352,14 @@ mod tests {
     #[test]
     fn parse_face() {
         let mut parser = Parser::new(&"", Options::default());
-        let result = parser.parse_face(0, &["3", "1", "2", "3"]);
+        let result = parser.parse_single_face(0, &["3", "1", "2", "3"]);
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
             Face {
-                vertices: vec![1, 2, 3],
-                color: None
+                vertices: vec![1, 2, 3].into(),
+                color: None,
+                color_index: None,
             }
         );
     }
@@ -810,13 +1367,14 @@ mod tests {
     #[test]
     fn parse_face_more() {
         let mut parser = Parser::new(&"", Options::default());
-        let result = parser.parse_face(0, &["4", "2", "3", "1", "1337"]);
+        let result = parser.parse_single_face(0, &["4", "2", "3", "1", "1337"]);
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
             Face {
-                vertices: vec![2, 3, 1, 1337],
-                color: None
+                vertices: vec![2, 3, 1, 1337].into(),
+                color: None,
+                color_index: None,
             }
         );
     }
@@ -824,7 +1382,7 @@ mod tests {
     #[test]
     fn parse_face_too_little_parts() {
         let mut parser = Parser::new(&"", Options::default());
-        let result = parser.parse_face(0, &["6", "1", "2", "3"]);
+        let result = parser.parse_single_face(0, &["6", "1", "2", "3"]);
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
@@ -838,7 +1396,7 @@ mod tests {
     #[test]
     fn parse_face_too_many_parts() {
         let mut parser = Parser::new(&"", Options::default());
-        let result = parser.parse_face(0, &["3", "2", "3", "2", "3"]);
+        let result = parser.parse_single_face(0, &["3", "2", "3", "2", "3", "4"]);
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
@@ -852,9 +1410,9 @@ mod tests {
     #[test]
     fn parse_face_no_number() {
         let mut parser = Parser::new(&"", Options::default());
-        let result = parser.parse_face(0, &["3", "1", "asdf", "3"]);
+        let result = parser.parse_single_face(0, &["3", "1", "asdf", "3"]);
         assert!(result.is_err());
-        println!("{:?}", result);
+        println!("{result:?}");
         assert!(matches!(
             result.unwrap_err(),
             Error {
@@ -867,18 +1425,19 @@ mod tests {
     #[test]
     fn parse_face_color() {
         let mut parser = Parser::new(&"", Options::default());
-        let result = parser.parse_face(0, &["3", "1", "2", "3", "0.1", "0.2", "0.3", "0.4"]);
+        let result = parser.parse_single_face(0, &["3", "1", "2", "3", "0.1", "0.2", "0.3", "0.4"]);
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
             Face {
-                vertices: vec![1, 2, 3],
+                vertices: vec![1, 2, 3].into(),
                 color: Some(Color {
                     red: 0.1,
                     green: 0.2,
                     blue: 0.3,
                     alpha: 0.4
-                })
+                }),
+                color_index: None,
             }
         );
     }
@@ -886,7 +1445,7 @@ mod tests {
     #[test]
     fn parse_face_color_fail() {
         let mut parser = Parser::new(&"", Options::default());
-        let result = parser.parse_face(0, &["3", "1", "2", "3", "0.1", "0.2"]);
+        let result = parser.parse_single_face(0, &["3", "1", "2", "3", "0.1", "0.2"]);
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
@@ -906,26 +1465,33 @@ mod tests {
                 ..Options::default()
             },
         );
-        let result = parser.parse_face(0, &["3", "1", "2", "3", "0.1", "0.2", "0.3"]);
+        let result = parser.parse_single_face(0, &["3", "1", "2", "3", "0.1", "0.2", "0.3"]);
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
             Face {
-                vertices: vec![1, 2, 3],
+                vertices: vec![1, 2, 3].into(),
                 color: Some(Color {
                     red: 0.1,
                     green: 0.2,
                     blue: 0.3,
                     alpha: 1.0
-                })
+                }),
+                color_index: None,
             }
         );
     }
 
     #[test]
     fn parse_face_color_fail_no_alpha_fail() {
-        let mut parser = Parser::new(&"", Options::default());
-        let result = parser.parse_face(0, &["3", "1", "2", "3", "0.1", "0.2", "0.3"]);
+        let mut parser = Parser::new(
+            &"",
+            Options {
+                color_format: ColorFormat::RGBAFloat,
+                ..Options::default()
+            },
+        );
+        let result = parser.parse_single_face(0, &["3", "1", "2", "3", "0.1", "0.2", "0.3"]);
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
@@ -938,26 +1504,34 @@ mod tests {
 
     #[test]
     fn parse_face_index() {
-        let result = Parser::parse_face_indices(0, 3, &["1", "2", "3"]);
+        let mut indices = Vec::new();
+        let result = Parser::parse_face_indices(0, 3, &["1", "2", "3"], &mut indices);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), vec![1, 2, 3]);
+        assert_eq!(indices, vec![1, 2, 3]);
     }
 
     #[test]
     fn parse_face_index_more() {
-        let result = Parser::parse_face_indices(0, 5, &["1", "2", "3", "1", "1337"]);
+        let mut indices = Vec::new();
+        let result = Parser::parse_face_indices(0, 5, &["1", "2", "3", "1", "1337"], &mut indices);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), vec![1, 2, 3, 1, 1337]);
+        assert_eq!(indices, vec![1, 2, 3, 1, 1337]);
     }
 
     #[test]
     fn parse_face_index_too_little_parts() {
-        let result = Parser::parse_face_indices(0, 5, &["1", "2", "3"]);
+        let mut parser = Parser::new(&"", Options::default());
+        let result = parser.parse_single_face(0, &["5", "1", "2", "3"]);
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
             Error {
-                kind: Kind::InvalidFaceIndex,
+                kind: Kind::InvalidFace,
+                details: Details::UnexpectedCount {
+                    element: Element::FaceVertex,
+                    actual: 3,
+                    ..
+                },
                 ..
             }
         ));
@@ -965,14 +1539,15 @@ mod tests {
 
     #[test]
     fn parse_face_index_too_many_parts() {
-        let result = Parser::parse_face_indices(0, 3, &["1", "2", "3", "2", "3"]);
+        let mut indices = vec![7];
+        let result = Parser::parse_face_indices(0, 3, &["1", "2", "3", "2", "3"], &mut indices);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), vec![1, 2, 3]);
+        assert_eq!(indices, vec![7, 1, 2, 3]);
     }
 
     #[test]
     fn parse_face_index_no_number() {
-        let result = Parser::parse_face_indices(0, 3, &["1", "asdf", "3"]);
+        let result = Parser::parse_face_indices(0, 3, &["1", "asdf", "3"], &mut Vec::new());
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
@@ -982,4 +1557,188 @@ mod tests {
             }
         ));
     }
+
+    #[test]
+    fn parse_auto_color_format() {
+        let options = Options {
+            color_format: ColorFormat::Auto,
+            ..Options::default()
+        };
+        let off = "OFF\n3 1\n0 0 0 255 0 0\n1 0 0\n0 1 0 0 0 255\n3 0 1 2 0 128 0\n";
+
+        let (mesh, color_format) = Parser::new(&off, options)
+            .parse_with_color_format()
+            .unwrap();
+        assert_eq!(color_format, ColorFormat::RGBInteger);
+        assert_eq!(
+            mesh.vertices[0].color,
+            Some(Color::new(1.0, 0.0, 0.0, 1.0).unwrap())
+        );
+        assert_eq!(mesh.vertices[1].color, None);
+    }
+
+    #[test]
+    fn parse_auto_color_format_without_colors() {
+        let options = Options {
+            color_format: ColorFormat::Auto,
+            ..Options::default()
+        };
+        let off = "OFF\n3 1\n0 0 0\n1 0 0\n0 1 0\n3 0 1 2\n";
+
+        let (_, color_format) = Parser::new(&off, options)
+            .parse_with_color_format()
+            .unwrap();
+        assert_eq!(color_format, ColorFormat::RGBAFloat);
+    }
+
+    #[test]
+    fn parse_auto_color_format_variable() {
+        let options = Options {
+            color_format: ColorFormat::Auto,
+            ..Options::default()
+        };
+        let off = "OFF\n3 1\n0 0 0 1 0 0\n1 0 0 1 0 0 0.5\n0 1 0\n3 0 1 2\n";
+
+        let (mesh, color_format) = Parser::new(&off, options)
+            .parse_with_color_format()
+            .unwrap();
+        assert_eq!(color_format, ColorFormat::Float);
+        assert_eq!(
+            mesh.vertices[0].color,
+            Some(Color::new(1.0, 0.0, 0.0, 1.0).unwrap())
+        );
+        assert_eq!(
+            mesh.vertices[1].color,
+            Some(Color::new(1.0, 0.0, 0.0, 0.5).unwrap())
+        );
+    }
+
+    #[test]
+    fn parse_default_color_format_variable() {
+        let off = "OFF\n3 1\n0 0 0 1 0 0\n1 0 0 1 0 0 0.5\n0 1 0\n3 0 1 2\n";
+
+        let mesh = Parser::new(&off, Options::default()).parse().unwrap();
+        assert_eq!(
+            mesh.vertices[0].color,
+            Some(Color::new(1.0, 0.0, 0.0, 1.0).unwrap())
+        );
+        assert_eq!(
+            mesh.vertices[1].color,
+            Some(Color::new(1.0, 0.0, 0.0, 0.5).unwrap())
+        );
+
+        assert!(Parser::new(
+            &off,
+            Options {
+                color_format: ColorFormat::RGBAFloat,
+                ..Options::default()
+            }
+        )
+        .parse()
+        .is_err());
+    }
+
+    #[test]
+    fn parse_auto_color_format_inconsistent() {
+        let options = Options {
+            color_format: ColorFormat::Auto,
+            ..Options::default()
+        };
+        let off = "OFF\n3 1\n0 0 0 1 0 0\n1 0 0 1 0\n0 1 0\n3 0 1 2\n";
+
+        let result = Parser::new(&off, options).parse();
+        assert!(matches!(
+            result.unwrap_err(),
+            crate::Error::ParserError(Error {
+                kind: Kind::InvalidColor,
+                line_index: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_faces_variable_channel_count() {
+        let mut parser = Parser::new(
+            &"3 0 1 2\n3 0 1 2 7\n3 0 1 2 255 0 0\n3 0 1 2 0 255 0 128",
+            Options {
+                color_format: ColorFormat::Integer,
+                ..Options::default()
+            },
+        );
+        parser.face_count = 4;
+        assert!(parser.parse_faces().is_ok());
+
+        let faces = &parser.document.faces;
+        assert_eq!(faces[0].color, None);
+        assert_eq!(faces[1].color_index, Some(7));
+        assert_eq!(
+            faces[2].color,
+            Some(Color::new(1.0, 0.0, 0.0, 1.0).unwrap())
+        );
+        assert_eq!(
+            faces[3].color,
+            Some(Color::new(0.0, 1.0, 0.0, 128.0 / 255.0).unwrap())
+        );
+    }
+
+    #[test]
+    fn parse_color_variable_channel_count_fail() {
+        let mut parser = Parser::new(
+            &"",
+            Options {
+                color_format: ColorFormat::Float,
+                ..Options::default()
+            },
+        );
+        let color = parser.parse_color(0, &["1.0", "0.5"]);
+        assert!(matches!(
+            color.unwrap_err(),
+            Error {
+                kind: Kind::InvalidColor,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parse_face_color_index() {
+        let mut parser = Parser::new(&"", Options::default());
+        let result = parser.parse_single_face(0, &["3", "1", "2", "3", "5"]);
+        assert_eq!(
+            result.unwrap(),
+            Face {
+                vertices: vec![1, 2, 3].into(),
+                color: Colormap::default().get(5),
+                color_index: Some(5),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_face_color_index_out_of_range() {
+        let mut parser = Parser::new(&"", Options::default());
+        let result = parser.parse_single_face(0, &["3", "1", "2", "3", "1000"]);
+        assert_eq!(
+            result.unwrap(),
+            Face {
+                vertices: vec![1, 2, 3].into(),
+                color: None,
+                color_index: Some(1000),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_face_color_index_no_integer() {
+        let mut parser = Parser::new(&"", Options::default());
+        let result = parser.parse_single_face(0, &["3", "1", "2", "3", "0.5"]);
+        assert!(matches!(
+            result.unwrap_err(),
+            Error {
+                kind: Kind::InvalidColor,
+                ..
+            }
+        ));
+    }
 }
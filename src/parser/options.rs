@@ -34,6 +34,19 @@ pub struct Limits {
 
     /// Defines the maximum amount of vertices per face the parser accepts.
     pub face_vertex_count: usize,
+
+    /// Defines the maximum size of the `off` data in bytes the parser accepts.
+    pub byte_count: usize,
+
+    /// Defines the maximum length of a line in bytes the parser accepts, excluding comments and surrounding
+    /// whitespace.
+    pub line_length: usize,
+
+    /// Defines the maximum amount of whitespace separated tokens per line the parser accepts.
+    pub token_count: usize,
+
+    /// Defines the maximum amount of vertex indices of all faces combined the parser accepts.
+    pub face_index_count: usize,
 }
 
 impl Default for Limits {
@@ -43,16 +56,38 @@ impl Default for Limits {
             vertex_count: 10000,
             face_count: 1000,
             face_vertex_count: 64,
+            byte_count: 64 * 1024 * 1024,
+            line_length: 4096,
+            token_count: 256,
+            face_index_count: 64_000,
         }
     }
 }
 
 impl Limits {
+    /// Returns the value of the given [`Limit`].
+    #[must_use]
+    pub fn get(&self, limit: Limit) -> usize {
+        match limit {
+            Limit::VertexCount => self.vertex_count,
+            Limit::FaceCount => self.face_count,
+            Limit::FaceVertexCount => self.face_vertex_count,
+            Limit::ByteCount => self.byte_count,
+            Limit::LineLength => self.line_length,
+            Limit::TokenCount => self.token_count,
+            Limit::FaceIndexCount => self.face_index_count,
+        }
+    }
+
     /// Limits instance with all values set to their respective maximum value.
     pub const MAX: Self = Self {
         vertex_count: usize::MAX,
         face_count: usize::MAX,
         face_vertex_count: usize::MAX,
+        byte_count: usize::MAX,
+        line_length: usize::MAX,
+        token_count: usize::MAX,
+        face_index_count: usize::MAX,
     };
 
     /// Limits instance with all values set to their respective minimum value.
@@ -60,5 +95,29 @@ impl Limits {
         vertex_count: usize::MIN,
         face_count: usize::MIN,
         face_vertex_count: usize::MIN,
+        byte_count: usize::MIN,
+        line_length: usize::MIN,
+        token_count: usize::MIN,
+        face_index_count: usize::MIN,
     };
 }
+
+/// Identifies a field of [`Limits`], e.g. in [`Details::LimitExceeded`](`super::error::Details::LimitExceeded`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Limit {
+    /// [`Limits::vertex_count`]
+    VertexCount,
+    /// [`Limits::face_count`]
+    FaceCount,
+    /// [`Limits::face_vertex_count`]
+    FaceVertexCount,
+    /// [`Limits::byte_count`]
+    ByteCount,
+    /// [`Limits::line_length`]
+    LineLength,
+    /// [`Limits::token_count`]
+    TokenCount,
+    /// [`Limits::face_index_count`]
+    FaceIndexCount,
+}
@@ -0,0 +1,301 @@
+use rayon::prelude::*;
+
+use super::{
+    error::{Details, Element, Error, Kind},
+    Parser, Result,
+};
+
+/// The number of lines parsed by a single task.
+const MIN_CHUNK_LEN: usize = 1024;
+
+/// The result of parsing a chunk of lines on a single thread.
+struct Chunk<T> {
+    /// The elements parsed before the first error.
+    elements: Vec<T>,
+    /// The face vertex indices of the parsed elements.
+    indices: Vec<usize>,
+    /// The first error of the chunk.
+    error: Option<Error>,
+}
+
+impl<'a> Parser<'a> {
+    /// Parses the `off` string like [`Parser::parse`], but parses the vertex and face lines on multiple threads.
+    ///
+    /// Returns the same [`crate::geometry::mesh::Mesh`] as [`Parser::parse`] and, if the data is invalid, the same
+    /// error (the one with the lowest line index).
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error` if an error occurs while parsing the `off` data.
+    pub fn parse_parallel(mut self) -> crate::Result {
+        self.check_byte_count()?;
+        self.parse_header()?;
+        self.parse_counts()?;
+
+        if self.color_format.is_auto() {
+            self.color_format = self.detect_color_format();
+        }
+
+        let vertex_lines = self.take_lines(self.vertex_count);
+        (self.document.vertices, _) = self.parse_lines(
+            &vertex_lines,
+            |line_index, parts, _| self.parse_vertex(line_index, parts),
+            |_, _, _| Ok(()),
+        )?;
+        self.check_missing(
+            Element::Vertex,
+            vertex_lines.len(),
+            self.vertex_count,
+            "Expected vertex definition",
+        )?;
+
+        let face_lines = self.take_lines(self.face_count);
+        let mut face_index_count = 0;
+        let indices;
+        (self.document.faces, indices) = self.parse_lines(
+            &face_lines,
+            |line_index, parts, indices| self.parse_face(line_index, parts, indices),
+            |line_index, face, offset| {
+                // The indices of the face are relative to the buffer of its chunk
+                face.vertices.shift(offset);
+                face_index_count += face.vertices.range().len();
+                self.check_face_index_count(line_index, face_index_count)
+            },
+        )?;
+        self.attach_indices(indices);
+        self.check_missing(
+            Element::Face,
+            face_lines.len(),
+            self.face_count,
+            "Expected face definition",
+        )?;
+
+        Ok(self.finalize())
+    }
+
+    /// Consumes up to `count` lines.
+    fn take_lines(&mut self, count: usize) -> Vec<(usize, &'a str)> {
+        let mut lines = Vec::with_capacity(count.min(self.lines.size_hint().1.unwrap_or(0)));
+
+        while lines.len() < count {
+            let Some(line) = self.next_line() else {
+                break;
+            };
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    /// Parses the lines in parallel and returns the results in order or the error with the lowest line index.
+    ///
+    /// Face vertex indices appended by `parse` are returned as a single buffer in line order. `check` is called
+    /// sequentially in line order for every parsed element with the offset of its chunk in that buffer, so checks
+    /// depending on the previous elements (e.g. running totals) fail at the same line as in [`Parser::parse`].
+    fn parse_lines<T, F, C>(
+        &self,
+        lines: &[(usize, &str)],
+        parse: F,
+        mut check: C,
+    ) -> Result<(Vec<T>, Vec<usize>)>
+    where
+        T: Send,
+        F: Fn(usize, &[&str], &mut Vec<usize>) -> Result<T> + Sync,
+        C: FnMut(usize, &mut T, usize) -> Result,
+    {
+        let chunks: Vec<Chunk<T>> = lines
+            .par_chunks(MIN_CHUNK_LEN)
+            .map(|lines| {
+                // The buffer is reused for all lines of the chunk to avoid an allocation per line
+                let mut parts = Vec::new();
+                let mut chunk = Chunk {
+                    elements: Vec::with_capacity(lines.len()),
+                    indices: Vec::new(),
+                    error: None,
+                };
+
+                for &(line_index, line) in lines {
+                    let element = self
+                        .tokenize(line_index, line, &mut parts)
+                        .and_then(|()| parse(line_index, &parts, &mut chunk.indices));
+
+                    match element {
+                        Ok(element) => chunk.elements.push(element),
+                        Err(err) => {
+                            chunk.error = Some(err);
+                            break;
+                        }
+                    }
+                }
+
+                chunk
+            })
+            .collect();
+
+        let mut elements = Vec::with_capacity(lines.len());
+        let mut indices = Vec::with_capacity(chunks.iter().map(|chunk| chunk.indices.len()).sum());
+        let mut line_indices = lines.iter().map(|&(line_index, _)| line_index);
+
+        // Merging sequentially stops at the first error in line order
+        for chunk in chunks {
+            let offset = indices.len();
+
+            for (mut element, line_index) in chunk.elements.into_iter().zip(line_indices.by_ref()) {
+                check(line_index, &mut element, offset)?;
+                elements.push(element);
+            }
+
+            if let Some(err) = chunk.error {
+                return Err(err);
+            }
+
+            indices.extend(chunk.indices);
+        }
+
+        Ok((elements, indices))
+    }
+
+    /// Returns a [`Kind::Missing`] error if less than `expected` lines were available.
+    fn check_missing(
+        &self,
+        element: Element,
+        actual: usize,
+        expected: usize,
+        message: &'static str,
+    ) -> Result {
+        if actual < expected {
+            return Err(
+                Error::with_message(Kind::Missing, self.prev_line_index + 1, message)
+                    .with_details(Details::exact_count(element, expected, actual)),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        geometry::mesh::Mesh,
+        parser::{
+            color_format::ColorFormat,
+            options::{Limits, Options},
+        },
+    };
+    use std::fmt::Write;
+
+    fn options() -> Options {
+        Options {
+            color_format: ColorFormat::Auto,
+            ..Options::default()
+        }
+    }
+
+    fn assert_same(off: &str) {
+        let serial = Parser::new(&off, options()).parse();
+        let parallel = Parser::new(&off, options()).parse_parallel();
+
+        match (serial, parallel) {
+            (Ok(serial), Ok(parallel)) => assert_eq!(serial, parallel),
+            (Err(crate::Error::ParserError(serial)), Err(crate::Error::ParserError(parallel))) => {
+                assert_eq!(serial, parallel);
+            }
+            (serial, parallel) => panic!("{serial:?} != {parallel:?}"),
+        }
+    }
+
+    #[test]
+    fn same_as_serial() {
+        assert_same("OFF\n3 1\n0 0 0\n1 0 0\n0 1 0 255 0 0\n3 0 1 2 7\n");
+        assert_same("");
+        assert_same("OFF\n");
+        assert_same("OFF\n3 1\n0 0 0\n1 0 0\n");
+        assert_same("OFF\n3 2\n0 0 0\n1 0 0\n0 1 0\n3 0 1 2\n");
+        assert_same("OFF\n3 1\n0 0 0\n1 a 0\n0 1\n3 0 1\n");
+        assert_same("OFF\n3 1\n0 0 0\n1 0 0\n0 1 0\n3 0 1\n");
+    }
+
+    #[test]
+    fn same_limits_as_serial() {
+        let limits = Limits {
+            line_length: 12,
+            token_count: 6,
+            face_index_count: 9,
+            ..Limits::default()
+        };
+        let assert_same_limits = |off: &str| {
+            let options = Options {
+                limits,
+                ..options()
+            };
+            match (
+                Parser::new(&off, options).parse(),
+                Parser::new(&off, options).parse_parallel(),
+            ) {
+                (
+                    Err(crate::Error::ParserError(serial)),
+                    Err(crate::Error::ParserError(parallel)),
+                ) => {
+                    assert_eq!(serial, parallel);
+                }
+                (serial, parallel) => panic!("{serial:?} != {parallel:?}"),
+            }
+        };
+
+        let vertices = "0 0 0\n1 0 0\n0 1 0\n";
+        assert_same_limits(&format!("OFF\n3 1\n{vertices}0 0 0.000001\n"));
+        assert_same_limits(&format!("OFF\n3 1\n{vertices}3 0 1 2 0 0 0 0\n"));
+        // The face index limit is exceeded before the invalid face
+        assert_same_limits(&format!(
+            "OFF\n3 4\n{vertices}3 0 1 2\n3 0 1 2\n4 0 1 2 0\nx\n"
+        ));
+    }
+
+    #[test]
+    fn first_error_of_many_lines() {
+        let mut off = String::from("OFF\n5000 0\n");
+        for i in 0..5000 {
+            if i % 1000 == 999 {
+                off.push_str("0 0 x\n");
+            } else {
+                off.push_str("0 0 0\n");
+            }
+        }
+
+        let error = Parser::new(&off, options()).parse_parallel().unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::ParserError(Error {
+                kind: Kind::InvalidVertexPosition,
+                line_index: 1001,
+                ..
+            })
+        ));
+        assert_same(&off);
+    }
+
+    #[test]
+    fn large_mesh() {
+        let mut off = String::from("OFF\n4000 3999\n");
+        for i in 0..4000 {
+            let _ = writeln!(off, "{i} 0.5 -{i} 0.1 0.2 0.3 1.0");
+        }
+        for i in 0..3999 {
+            let _ = writeln!(off, "3 {i} {} 0", i + 1);
+        }
+
+        let mesh: Mesh = Parser::new(
+            &off,
+            Options {
+                limits: Limits::MAX,
+                ..options()
+            },
+        )
+        .parse_parallel()
+        .unwrap();
+        assert_eq!(mesh.vertex_count(), 4000);
+        assert_eq!(mesh.face_count(), 3999);
+    }
+}
@@ -1,17 +1,251 @@
-use std::str::FromStr;
+use std::{
+    num::ParseFloatError,
+    str::{FromStr, SplitWhitespace},
+};
 
 /// A trait defining the interface for spliting a string into a vector of strings.
 pub(crate) trait StrParts<'a> {
+    #[cfg(any(test, fuzzing))]
     fn split_line(self) -> Vec<&'a str>;
+    fn tokens(self) -> Tokens<'a>;
 }
 
 impl<'a> StrParts<'a> for &'a str {
     /// Splits a string into a vector of strings at whitespaces and ignores comments.
+    #[cfg(any(test, fuzzing))]
     fn split_line(self) -> Vec<&'a str> {
-        self.split_whitespace()
-            .map_while(|s| (!s.starts_with('#')).then(|| s))
-            // .map_while(|s| (!s.starts_with('#')).then_some(s)); currently still unstable (https://github.com/rust-lang/rust/issues/80967)
-            .collect()
+        self.tokens().collect()
+    }
+
+    /// Returns an iterator over the whitespace separated parts of a string, which stops at comments.
+    fn tokens(self) -> Tokens<'a> {
+        if self.is_ascii() {
+            Tokens::Ascii {
+                line: self,
+                position: 0,
+            }
+        } else {
+            Tokens::Unicode(self.split_whitespace())
+        }
+    }
+}
+
+/// An iterator over the whitespace separated parts of a line, which stops at the first part starting with `#`.
+///
+/// ASCII lines are split by scanning their bytes, all other lines fall back to [`str::split_whitespace`].
+#[derive(Debug, Clone)]
+pub(crate) enum Tokens<'a> {
+    Ascii { line: &'a str, position: usize },
+    Unicode(SplitWhitespace<'a>),
+    Done,
+}
+
+/// Returns whether the byte is an ASCII character with the Unicode `White_Space` property.
+fn is_whitespace(byte: u8) -> bool {
+    // Unlike `u8::is_ascii_whitespace` this includes the vertical tab, matching `char::is_whitespace`
+    matches!(byte, b' ' | b'\t' | b'\n' | 0x0B | 0x0C | b'\r')
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = match self {
+            Tokens::Ascii { line, position } => {
+                let bytes = line.as_bytes();
+                let start = *position
+                    + bytes[*position..]
+                        .iter()
+                        .position(|&byte| !is_whitespace(byte))
+                        .unwrap_or(bytes.len() - *position);
+                let end = start
+                    + bytes[start..]
+                        .iter()
+                        .position(|&byte| is_whitespace(byte))
+                        .unwrap_or(bytes.len() - start);
+                *position = end;
+
+                // Only ASCII bytes are split, so both indices are char boundaries
+                (start < end).then(|| &line[start..end])
+            }
+            Tokens::Unicode(parts) => parts.next(),
+            Tokens::Done => None,
+        };
+
+        match token {
+            Some(token) if !token.starts_with('#') => Some(token),
+            _ => {
+                *self = Tokens::Done;
+                None
+            }
+        }
+    }
+}
+
+/// A trait defining the interface for parsing numbers faster than [`FromStr`] with identical results.
+pub(crate) trait FastParse: FromStr {
+    /// Parses the string, returning the same value or error as [`FromStr::from_str`].
+    fn parse_fast(s: &str) -> Result<Self, Self::Err>;
+}
+
+/// Exactly representable powers of ten for the fast float path.
+const F32_POWERS_OF_TEN: [f32; 11] = [1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10];
+
+/// Parses simple decimal numbers using Clinger's fast path.
+///
+/// If the decimal mantissa and the power of ten are both exactly representable as [`f32`], a single
+/// multiplication or division yields the correctly rounded result. Returns `None` for all other inputs.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn fast_f32(bytes: &[u8]) -> Option<f32> {
+    let (negative, mut bytes) = match bytes.first()? {
+        b'-' => (true, &bytes[1..]),
+        b'+' => (false, &bytes[1..]),
+        _ => (false, bytes),
+    };
+
+    let mut mantissa: u64 = 0;
+    let mut exponent: i64 = 0;
+    let mut digit_count = 0;
+    let mut significant_digits = 0;
+    let mut fraction = false;
+
+    while let Some((&byte, rest)) = bytes.split_first() {
+        match byte {
+            b'0'..=b'9' => {
+                digit_count += 1;
+                if mantissa != 0 || byte != b'0' {
+                    significant_digits += 1;
+                }
+                if significant_digits > 19 {
+                    return None;
+                }
+                mantissa = mantissa * 10 + u64::from(byte - b'0');
+                if fraction {
+                    exponent -= 1;
+                }
+            }
+            b'.' if !fraction => fraction = true,
+            b'e' | b'E' => break,
+            _ => return None,
+        }
+        bytes = rest;
+    }
+
+    if digit_count == 0 {
+        return None;
+    }
+
+    if let Some((_, rest)) = bytes.split_first() {
+        // `bytes` starts with the exponent marker
+        let (exponent_negative, digits) = match rest.first()? {
+            b'-' => (true, &rest[1..]),
+            b'+' => (false, &rest[1..]),
+            _ => (false, rest),
+        };
+
+        if digits.is_empty() || digits.len() > 4 || !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+
+        let value = digits
+            .iter()
+            .fold(0, |value, &digit| value * 10 + i64::from(digit - b'0'));
+        exponent += if exponent_negative { -value } else { value };
+    }
+
+    let value = if mantissa == 0 {
+        0.0
+    } else {
+        if mantissa > 1 << f32::MANTISSA_DIGITS {
+            return None;
+        }
+
+        let power = *F32_POWERS_OF_TEN.get(usize::try_from(exponent.unsigned_abs()).ok()?)?;
+
+        if exponent < 0 {
+            mantissa as f32 / power
+        } else {
+            mantissa as f32 * power
+        }
+    };
+
+    Some(if negative { -value } else { value })
+}
+
+impl FastParse for f32 {
+    fn parse_fast(s: &str) -> Result<Self, Self::Err> {
+        match fast_f32(s.as_bytes()) {
+            Some(value) => Ok(value),
+            None => s.parse(),
+        }
+    }
+}
+
+/// Implements [`FastParse`] for unsigned integers by accumulating plain digit strings and falling back to
+/// [`FromStr`] for everything else, including overflows.
+macro_rules! fast_parse_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl FastParse for $ty {
+                fn parse_fast(s: &str) -> Result<Self, Self::Err> {
+                    let bytes = s.as_bytes();
+
+                    if !bytes.is_empty() && bytes.len() <= 19 && bytes.iter().all(u8::is_ascii_digit) {
+                        let value = bytes
+                            .iter()
+                            .fold(0u64, |value, &digit| value * 10 + u64::from(digit - b'0'));
+
+                        if let Ok(value) = <$ty>::try_from(value) {
+                            return Ok(value);
+                        }
+                    }
+
+                    s.parse()
+                }
+            }
+        )*
+    };
+}
+
+fast_parse_unsigned!(u8, usize);
+
+/// Tokenizes the line and parses every part as [`f32`], like the parser does for vertex lines.
+///
+/// Used by the `parse` benchmark to compare against [`str::split_whitespace`] and [`str::parse`].
+///
+/// # Errors
+///
+/// Returns the error of the first part that is not a number.
+#[doc(hidden)]
+pub fn parse_floats(line: &str) -> Result<Vec<f32>, ParseFloatError> {
+    line.tokens().map(f32::parse_fast).collect()
+}
+
+/// Checks that the tokenizer and number parsing match the standard library for the given line.
+///
+/// Used by the unit tests and the `tokenizer` fuzz target.
+#[cfg(any(test, fuzzing))]
+pub fn check_parity(line: &str) {
+    let expected: Vec<&str> = line
+        .split_whitespace()
+        .map_while(|s| (!s.starts_with('#')).then_some(s))
+        .collect();
+    assert_eq!(line.split_line(), expected, "tokens of {line:?}");
+
+    for token in expected {
+        match (f32::parse_fast(token), token.parse::<f32>()) {
+            (Ok(fast), Ok(std)) => assert!(
+                fast.to_bits() == std.to_bits() || (fast.is_nan() && std.is_nan()),
+                "f32 of {token:?}: {fast} != {std}"
+            ),
+            (fast, std) => assert_eq!(fast, std, "f32 of {token:?}"),
+        }
+        assert_eq!(
+            usize::parse_fast(token),
+            token.parse(),
+            "usize of {token:?}"
+        );
+        assert_eq!(u8::parse_fast(token), token.parse(), "u8 of {token:?}");
     }
 }
 
@@ -46,6 +280,96 @@ mod tests {
         assert_eq!("1   2      3.0".split_line(), vec!["1", "2", "3.0"]);
     }
 
+    #[test]
+    fn tokens() {
+        assert_eq!("1 2 # 3".tokens().collect::<Vec<_>>(), vec!["1", "2"]);
+        assert_eq!("1\u{0B}2\u{a0}3".split_line(), vec!["1", "2", "3"]);
+        assert_eq!(" \t ".split_line(), Vec::<&str>::new());
+        assert_eq!("a #b c".split_line(), vec!["a"]);
+    }
+
+    #[test]
+    fn parse_floats() {
+        assert_eq!(
+            super::parse_floats("1 -2.5 3e1 # 4"),
+            Ok(vec![1.0, -2.5, 30.0])
+        );
+        assert!(super::parse_floats("1 x").is_err());
+    }
+
+    #[test]
+    fn parse_fast() {
+        assert_eq!(f32::parse_fast("1.5"), Ok(1.5));
+        assert_eq!(f32::parse_fast("-0.000001"), Ok(-0.000_001));
+        assert_eq!(f32::parse_fast("1e3"), Ok(1000.0));
+        assert_eq!(f32::parse_fast("1.632993"), "1.632993".parse());
+        assert!(f32::parse_fast("-0").unwrap().is_sign_negative());
+        assert_eq!(usize::parse_fast("1337"), Ok(1337));
+        assert_eq!(u8::parse_fast("255"), Ok(255));
+        assert!(u8::parse_fast("256").is_err());
+        assert!(f32::parse_fast("1.0.0").is_err());
+    }
+
+    #[test]
+    fn parity() {
+        let tokens = [
+            "",
+            "0",
+            "-0",
+            "+0",
+            "00",
+            "1",
+            "255",
+            "256",
+            "-1",
+            "+1",
+            "1.",
+            ".5",
+            ".",
+            "-.5",
+            "1.5e",
+            "1e+",
+            "1e-45",
+            "1e-46",
+            "3.4028235e38",
+            "3.4028236e38",
+            "1e39",
+            "16777216",
+            "16777217",
+            "0.1",
+            "0.3",
+            "1.632993",
+            "123456789012345678901234567890",
+            "0.000000000000000000000000001",
+            "18446744073709551615",
+            "18446744073709551616",
+            "9999999999999999999",
+            "1e10",
+            "1e11",
+            "1.5e-10",
+            "1_0",
+            "inf",
+            "-infinity",
+            "NaN",
+            "nan",
+            "1e99999",
+            "1e-99999",
+            "0x10",
+            "1,5",
+            "4294967296",
+            "1E5",
+            "e5",
+            "-",
+            "+",
+        ];
+
+        for token in tokens {
+            check_parity(token);
+        }
+        check_parity("1  2\t3 # 4");
+        check_parity("\u{85}1\u{2003}2");
+    }
+
     #[test]
     fn convert_vec() {
         assert_eq!(
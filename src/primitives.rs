@@ -0,0 +1,632 @@
+//! Generators for common shapes.
+//!
+//! All meshes are centered at the origin and their faces are wound counter-clockwise when viewed from the outside, so
+//! [`Mesh::face_normal`] points outwards. Shapes with a main axis (cylinder, cone, torus and the poles of the UV
+//! sphere) are aligned with the z axis. Resolution parameters below their minimum are raised to it.
+
+use std::{collections::HashMap, f32::consts::PI};
+
+use crate::geometry::{builder::MeshBuilder, mesh::Mesh, position::Position};
+
+/// The five Platonic solids, see [`platonic`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Platonic {
+    /// Four triangles.
+    Tetrahedron,
+    /// Six squares, a cube.
+    Hexahedron,
+    /// Eight triangles.
+    Octahedron,
+    /// Twelve pentagons.
+    Dodecahedron,
+    /// Twenty triangles.
+    Icosahedron,
+}
+
+/// Creates a cube with the given edge length made of six quads.
+#[must_use]
+pub fn cube(size: f32) -> Mesh {
+    let half = size / 2.0;
+    let positions = (0..8)
+        .map(|i| {
+            let sign = |bit: usize| if i & bit == 0 { -half } else { half };
+            Position::new(sign(1), sign(2), sign(4))
+        })
+        .collect();
+
+    let faces = [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ];
+
+    build(positions, faces)
+}
+
+/// Creates a Platonic solid whose vertices lie on a sphere with the given radius.
+#[must_use]
+pub fn platonic(solid: Platonic, radius: f32) -> Mesh {
+    let (positions, faces) = match solid {
+        Platonic::Tetrahedron => {
+            let positions = [
+                [1.0, 1.0, 1.0],
+                [1.0, -1.0, -1.0],
+                [-1.0, 1.0, -1.0],
+                [-1.0, -1.0, 1.0],
+            ];
+            let faces = vec![vec![0, 1, 2], vec![0, 3, 1], vec![0, 2, 3], vec![1, 3, 2]];
+            (
+                positions.map(|[x, y, z]| Position::new(x, y, z)).to_vec(),
+                faces,
+            )
+        }
+        Platonic::Hexahedron => {
+            let mesh = cube(1.0);
+            let faces = mesh
+                .faces
+                .iter()
+                .map(|face| face.vertices.to_vec())
+                .collect();
+            let positions = mesh.vertices.iter().map(|vertex| vertex.position).collect();
+            (positions, faces)
+        }
+        Platonic::Octahedron => {
+            let positions = vec![
+                Position::new(1.0, 0.0, 0.0),
+                Position::new(-1.0, 0.0, 0.0),
+                Position::new(0.0, 1.0, 0.0),
+                Position::new(0.0, -1.0, 0.0),
+                Position::new(0.0, 0.0, 1.0),
+                Position::new(0.0, 0.0, -1.0),
+            ];
+            let faces = (0..8)
+                .map(|i| vec![i & 1, 2 + (i >> 1 & 1), 4 + (i >> 2 & 1)])
+                .collect();
+            (positions, faces)
+        }
+        Platonic::Dodecahedron => {
+            let (positions, faces) = icosahedron();
+            dual(&positions, &faces)
+        }
+        Platonic::Icosahedron => icosahedron(),
+    };
+
+    let positions: Vec<Position> = positions
+        .into_iter()
+        .map(|position| position.normalized() * radius)
+        .collect();
+    let faces = faces
+        .into_iter()
+        .map(|face| orient_outwards(&positions, face))
+        .collect::<Vec<_>>();
+
+    build(positions, faces)
+}
+
+/// Creates a sphere from `segments` meridians and `rings` parallels.
+///
+/// The poles are single vertices surrounded by triangles, all other faces are quads. `segments` is at least three and
+/// `rings` at least two.
+#[must_use]
+pub fn uv_sphere(radius: f32, segments: usize, rings: usize) -> Mesh {
+    let segments = segments.max(3);
+    let rings = rings.max(2);
+
+    let mut builder = MeshBuilder::with_capacity(segments * (rings - 1) + 2, segments * rings);
+    let top = builder.add_vertex(Position::new(0.0, 0.0, radius));
+
+    for ring in 1..rings {
+        let theta = PI * fraction(ring, rings);
+
+        for segment in 0..segments {
+            let phi = 2.0 * PI * fraction(segment, segments);
+            builder.add_vertex(Position::new(
+                radius * theta.sin() * phi.cos(),
+                radius * theta.sin() * phi.sin(),
+                radius * theta.cos(),
+            ));
+        }
+    }
+
+    let bottom = builder.add_vertex(Position::new(0.0, 0.0, -radius));
+    let vertex = |ring: usize, segment: usize| 1 + (ring - 1) * segments + segment % segments;
+
+    for segment in 0..segments {
+        builder.add_face([top, vertex(1, segment), vertex(1, segment + 1)]);
+    }
+
+    for ring in 1..rings - 1 {
+        for segment in 0..segments {
+            builder.add_face([
+                vertex(ring, segment),
+                vertex(ring + 1, segment),
+                vertex(ring + 1, segment + 1),
+                vertex(ring, segment + 1),
+            ]);
+        }
+    }
+
+    for segment in 0..segments {
+        builder.add_face([
+            vertex(rings - 1, segment),
+            bottom,
+            vertex(rings - 1, segment + 1),
+        ]);
+    }
+
+    builder.build_unchecked()
+}
+
+/// Creates a sphere by repeatedly splitting every triangle of an icosahedron into four.
+///
+/// The sphere has `20 * 4^subdivisions` triangles of nearly equal size.
+#[must_use]
+pub fn icosphere(radius: f32, subdivisions: usize) -> Mesh {
+    let (mut positions, faces) = icosahedron();
+    let mut faces: Vec<[usize; 3]> = faces
+        .into_iter()
+        .map(|face| {
+            let face = orient_outwards(&positions, face);
+            [face[0], face[1], face[2]]
+        })
+        .collect();
+
+    for _ in 0..subdivisions {
+        // Edges are shared by two triangles, which have to use the same midpoint
+        let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut midpoint = |a: usize, b: usize| {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                positions.push(((positions[a] + positions[b]) * 0.5).normalized());
+                positions.len() - 1
+            })
+        };
+
+        faces = faces
+            .iter()
+            .flat_map(|&[a, b, c]| {
+                let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+                [[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]]
+            })
+            .collect();
+    }
+
+    let positions = positions
+        .into_iter()
+        .map(|position| position.normalized() * radius)
+        .collect();
+
+    build(positions, faces)
+}
+
+/// Creates a closed cylinder with `segments` quads around its side and an n-gon for each cap.
+///
+/// `segments` is at least three.
+#[must_use]
+pub fn cylinder(radius: f32, height: f32, segments: usize) -> Mesh {
+    let segments = segments.max(3);
+    let mut builder = MeshBuilder::with_capacity(2 * segments, segments + 2);
+
+    for z in [-height / 2.0, height / 2.0] {
+        for position in circle(radius, segments) {
+            builder.add_vertex(Position::new(position.x, position.y, z));
+        }
+    }
+
+    for i in 0..segments {
+        let next = (i + 1) % segments;
+        builder.add_face([i, next, segments + next, segments + i]);
+    }
+
+    builder.add_face((0..segments).rev());
+    builder.add_face(segments..2 * segments);
+
+    builder.build_unchecked()
+}
+
+/// Creates a cone with its base centered at `-height / 2` and its apex at `height / 2`.
+///
+/// The base is a single n-gon with `segments` vertices, `segments` is at least three.
+#[must_use]
+pub fn cone(radius: f32, height: f32, segments: usize) -> Mesh {
+    let segments = segments.max(3);
+    let mut builder = MeshBuilder::with_capacity(segments + 1, segments + 1);
+
+    for position in circle(radius, segments) {
+        builder.add_vertex(Position::new(position.x, position.y, -height / 2.0));
+    }
+    let apex = builder.add_vertex(Position::new(0.0, 0.0, height / 2.0));
+
+    for i in 0..segments {
+        builder.add_face([i, (i + 1) % segments, apex]);
+    }
+    builder.add_face((0..segments).rev());
+
+    builder.build_unchecked()
+}
+
+/// Creates a torus around the z axis made of quads.
+///
+/// `major_radius` is the distance from the center to the center of the tube and `minor_radius` the radius of the
+/// tube. The ring is divided into `major_segments` and the tube into `minor_segments`, both at least three.
+#[must_use]
+pub fn torus(
+    major_radius: f32,
+    minor_radius: f32,
+    major_segments: usize,
+    minor_segments: usize,
+) -> Mesh {
+    let major_segments = major_segments.max(3);
+    let minor_segments = minor_segments.max(3);
+    let mut builder = MeshBuilder::with_capacity(
+        major_segments * minor_segments,
+        major_segments * minor_segments,
+    );
+
+    for i in 0..major_segments {
+        let u = 2.0 * PI * fraction(i, major_segments);
+
+        for j in 0..minor_segments {
+            let v = 2.0 * PI * fraction(j, minor_segments);
+            let distance = major_radius + minor_radius * v.cos();
+
+            builder.add_vertex(Position::new(
+                distance * u.cos(),
+                distance * u.sin(),
+                minor_radius * v.sin(),
+            ));
+        }
+    }
+
+    let vertex = |i: usize, j: usize| (i % major_segments) * minor_segments + j % minor_segments;
+
+    for i in 0..major_segments {
+        for j in 0..minor_segments {
+            builder.add_face([
+                vertex(i, j),
+                vertex(i + 1, j),
+                vertex(i + 1, j + 1),
+                vertex(i, j + 1),
+            ]);
+        }
+    }
+
+    builder.build_unchecked()
+}
+
+/// Creates a flat grid of `columns` by `rows` quads in the xy plane facing the positive z axis.
+///
+/// The grid spans `width` along the x axis and `depth` along the y axis. `columns` and `rows` are at least one.
+#[must_use]
+pub fn plane(width: f32, depth: f32, columns: usize, rows: usize) -> Mesh {
+    let columns = columns.max(1);
+    let rows = rows.max(1);
+    let mut builder = MeshBuilder::with_capacity((columns + 1) * (rows + 1), columns * rows);
+
+    for row in 0..=rows {
+        for column in 0..=columns {
+            builder.add_vertex(Position::new(
+                width * (fraction(column, columns) - 0.5),
+                depth * (fraction(row, rows) - 0.5),
+                0.0,
+            ));
+        }
+    }
+
+    let vertex = |column: usize, row: usize| row * (columns + 1) + column;
+
+    for row in 0..rows {
+        for column in 0..columns {
+            builder.add_face([
+                vertex(column, row),
+                vertex(column + 1, row),
+                vertex(column + 1, row + 1),
+                vertex(column, row + 1),
+            ]);
+        }
+    }
+
+    builder.build_unchecked()
+}
+
+/// Returns `index / count` as a float.
+#[allow(clippy::cast_precision_loss)]
+fn fraction(index: usize, count: usize) -> f32 {
+    index as f32 / count as f32
+}
+
+/// Returns `segments` points on a circle in the xy plane in counter-clockwise order.
+fn circle(radius: f32, segments: usize) -> impl Iterator<Item = Position> {
+    (0..segments).map(move |i| {
+        let angle = 2.0 * PI * fraction(i, segments);
+        Position::new(radius * angle.cos(), radius * angle.sin(), 0.0)
+    })
+}
+
+/// Returns the vertices and faces of an icosahedron whose vertices lie on the unit sphere.
+fn icosahedron() -> (Vec<Position>, Vec<Vec<usize>>) {
+    let t = f32::midpoint(1.0, 5.0_f32.sqrt());
+
+    let positions = [
+        [-1.0, t, 0.0],
+        [1.0, t, 0.0],
+        [-1.0, -t, 0.0],
+        [1.0, -t, 0.0],
+        [0.0, -1.0, t],
+        [0.0, 1.0, t],
+        [0.0, -1.0, -t],
+        [0.0, 1.0, -t],
+        [t, 0.0, -1.0],
+        [t, 0.0, 1.0],
+        [-t, 0.0, -1.0],
+        [-t, 0.0, 1.0],
+    ]
+    .map(|[x, y, z]| Position::new(x, y, z).normalized())
+    .to_vec();
+
+    let faces = [
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ]
+    .map(|face| face.to_vec())
+    .to_vec();
+
+    (positions, faces)
+}
+
+/// Returns the dual of a convex polyhedron centered at the origin.
+///
+/// Every face becomes a vertex at its centroid and every vertex a face connecting the centroids of its faces.
+fn dual(positions: &[Position], faces: &[Vec<usize>]) -> (Vec<Position>, Vec<Vec<usize>>) {
+    let centroids: Vec<Position> = faces.iter().map(|face| centroid(positions, face)).collect();
+
+    let dual_faces = positions
+        .iter()
+        .enumerate()
+        .map(|(vertex, &axis)| {
+            let mut adjacent: Vec<usize> = (0..faces.len())
+                .filter(|&face| faces[face].contains(&vertex))
+                .collect();
+
+            // Order the faces by their angle around the vertex
+            let reference = centroids[adjacent[0]] - axis;
+            let tangent = axis.cross(reference);
+            adjacent.sort_by(|&a, &b| {
+                let angle = |face: usize| {
+                    let offset = centroids[face] - axis;
+                    offset.dot(tangent).atan2(offset.dot(reference))
+                };
+                angle(a).total_cmp(&angle(b))
+            });
+
+            adjacent
+        })
+        .collect();
+
+    (centroids, dual_faces)
+}
+
+/// Returns the centroid of the vertices of a face.
+fn centroid(positions: &[Position], face: &[usize]) -> Position {
+    let sum = face
+        .iter()
+        .fold(Position::default(), |sum, &index| sum + positions[index]);
+
+    sum * (1.0 / fraction(face.len(), 1))
+}
+
+/// Reverses the face if it is wound clockwise when viewed from the outside of a convex shape centered at the origin.
+fn orient_outwards(positions: &[Position], mut face: Vec<usize>) -> Vec<usize> {
+    let a = positions[face[0]];
+    let normal = (positions[face[1]] - a).cross(positions[face[2]] - a);
+
+    if normal.dot(centroid(positions, &face)) < 0.0 {
+        face.reverse();
+    }
+
+    face
+}
+
+/// Creates a mesh from positions and faces.
+fn build<F, I>(positions: Vec<Position>, faces: F) -> Mesh
+where
+    F: IntoIterator<Item = I>,
+    I: IntoIterator<Item = usize>,
+{
+    let mut builder = MeshBuilder::with_capacity(positions.len(), 0);
+
+    for position in positions {
+        builder.add_vertex(position);
+    }
+
+    for face in faces {
+        builder.add_face(face);
+    }
+
+    builder.build_unchecked()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Asserts that every edge is shared by exactly two faces which traverse it in opposite directions.
+    fn assert_closed(mesh: &Mesh) {
+        let mut edges = HashSet::new();
+
+        for face in &mesh.faces {
+            for (i, &a) in face.vertices.iter().enumerate() {
+                let b = face.vertices[(i + 1) % face.vertices.len()];
+                assert!(edges.insert((a, b)), "edge ({a}, {b}) used twice");
+            }
+        }
+
+        for &(a, b) in &edges {
+            assert!(edges.contains(&(b, a)), "edge ({a}, {b}) is a boundary");
+        }
+
+        // Euler characteristic of a sphere
+        let euler = mesh.vertex_count() + mesh.face_count() - edges.len() / 2;
+        assert_eq!(euler, 2);
+    }
+
+    /// Asserts that all face normals point away from the origin.
+    fn assert_outwards(mesh: &Mesh) {
+        for (index, face) in mesh.faces.iter().enumerate() {
+            let normal = mesh.face_normal(index).unwrap();
+            let center = face.vertices.iter().fold(Position::default(), |sum, &i| {
+                sum + mesh.vertices[i].position
+            });
+            assert!(normal.dot(center) > 0.0, "face {index} points inwards");
+        }
+    }
+
+    fn assert_radius(mesh: &Mesh, radius: f32) {
+        for vertex in &mesh.vertices {
+            assert!((vertex.position.length() - radius).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn cube() {
+        let mesh = super::cube(2.0);
+        assert_eq!((mesh.vertex_count(), mesh.face_count()), (8, 6));
+        assert_eq!(
+            mesh.bounding_box(),
+            Some((
+                Position::new(-1.0, -1.0, -1.0),
+                Position::new(1.0, 1.0, 1.0)
+            ))
+        );
+        assert_closed(&mesh);
+        assert_outwards(&mesh);
+    }
+
+    #[test]
+    fn platonic() {
+        for (solid, vertex_count, face_count, face_len) in [
+            (Platonic::Tetrahedron, 4, 4, 3),
+            (Platonic::Hexahedron, 8, 6, 4),
+            (Platonic::Octahedron, 6, 8, 3),
+            (Platonic::Dodecahedron, 20, 12, 5),
+            (Platonic::Icosahedron, 12, 20, 3),
+        ] {
+            let mesh = super::platonic(solid, 2.0);
+            assert_eq!(mesh.vertex_count(), vertex_count, "{solid:?}");
+            assert_eq!(mesh.face_count(), face_count, "{solid:?}");
+            assert!(mesh
+                .faces
+                .iter()
+                .all(|face| face.vertices.len() == face_len));
+            assert_closed(&mesh);
+            assert_outwards(&mesh);
+            assert_radius(&mesh, 2.0);
+        }
+    }
+
+    #[test]
+    fn uv_sphere() {
+        let mesh = super::uv_sphere(1.5, 8, 4);
+        assert_eq!(mesh.vertex_count(), 8 * 3 + 2);
+        assert_eq!(mesh.face_count(), 8 * 4);
+        assert_closed(&mesh);
+        assert_outwards(&mesh);
+        assert_radius(&mesh, 1.5);
+
+        let minimal = super::uv_sphere(1.0, 0, 0);
+        assert_eq!(minimal.vertex_count(), 3 + 2);
+        assert_closed(&minimal);
+    }
+
+    #[test]
+    fn icosphere() {
+        for subdivisions in 0..3_u32 {
+            let mesh = super::icosphere(1.0, subdivisions as usize);
+            assert_eq!(mesh.face_count(), 20 * 4_usize.pow(subdivisions));
+            assert_closed(&mesh);
+            assert_outwards(&mesh);
+            assert_radius(&mesh, 1.0);
+        }
+    }
+
+    #[test]
+    fn cylinder() {
+        let mesh = super::cylinder(1.0, 2.0, 6);
+        assert_eq!((mesh.vertex_count(), mesh.face_count()), (12, 8));
+        assert_closed(&mesh);
+        assert_outwards(&mesh);
+    }
+
+    #[test]
+    fn cone() {
+        let mesh = super::cone(1.0, 2.0, 5);
+        assert_eq!((mesh.vertex_count(), mesh.face_count()), (6, 6));
+        assert_closed(&mesh);
+        assert_outwards(&mesh);
+    }
+
+    #[test]
+    fn torus() {
+        let mesh = super::torus(2.0, 0.5, 8, 6);
+        assert_eq!((mesh.vertex_count(), mesh.face_count()), (48, 48));
+
+        // Every edge is shared by two faces, but the torus has genus one
+        let edges: usize = mesh
+            .faces
+            .iter()
+            .map(|face| face.vertices.len())
+            .sum::<usize>()
+            / 2;
+        assert_eq!(mesh.vertex_count() + mesh.face_count(), edges);
+
+        // Every normal points away from the center line of the tube
+        for (index, face) in mesh.faces.iter().enumerate() {
+            let center = centroid(
+                &mesh
+                    .vertices
+                    .iter()
+                    .map(|vertex| vertex.position)
+                    .collect::<Vec<_>>(),
+                &face.vertices,
+            );
+            let tube = Position::new(center.x, center.y, 0.0).normalized() * 2.0;
+            let normal = mesh.face_normal(index).unwrap();
+            assert!(
+                normal.dot(center - tube) > 0.0,
+                "face {index} points inwards"
+            );
+        }
+    }
+
+    #[test]
+    fn plane() {
+        let mesh = super::plane(4.0, 2.0, 4, 2);
+        assert_eq!((mesh.vertex_count(), mesh.face_count()), (15, 8));
+        assert_eq!(
+            mesh.bounding_box(),
+            Some((Position::new(-2.0, -1.0, 0.0), Position::new(2.0, 1.0, 0.0)))
+        );
+        assert!((0..8).all(|i| mesh.face_normal(i) == Some(Position::new(0.0, 0.0, 1.0))));
+    }
+}
@@ -0,0 +1,203 @@
+//! Sampling of meshes onto regular 3D grids, for example to voxelize models for machine learning.
+//!
+//! Samples are stored in a flat [`Vec`] with the x coordinate changing fastest, followed by y and z, see
+//! [`Grid::index`]. With the `rayon` feature the samples are computed in parallel.
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{bvh::Bvh, geometry::position::Position};
+
+/// A regular grid of cubic cells, which are sampled at their centers.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Grid {
+    /// The minimum corner of the grid.
+    pub origin: Position,
+    /// The edge length of a cell.
+    pub cell_size: f32,
+    /// The number of cells along the x, y and z axis.
+    pub dimensions: [usize; 3],
+}
+
+impl Grid {
+    /// Creates a new [`Grid`].
+    #[must_use]
+    pub fn new(origin: Position, cell_size: f32, dimensions: [usize; 3]) -> Self {
+        Self {
+            origin,
+            cell_size,
+            dimensions,
+        }
+    }
+
+    /// Creates a grid around the box from `min` to `max` with `resolution` cells along its longest axis.
+    ///
+    /// The other axes get as many cells as needed to cover the box, at least one, and the grid is centered on the box.
+    /// A `resolution` of zero is treated as one.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn fit(min: Position, max: Position, resolution: usize) -> Self {
+        let size = max - min;
+        let longest = size.x.max(size.y).max(size.z);
+        let cell_size = if longest > 0.0 {
+            longest / resolution.max(1) as f32
+        } else {
+            1.0
+        };
+
+        let cells =
+            |length: f32| ((length / cell_size).ceil() as usize).clamp(1, resolution.max(1));
+        let dimensions = [cells(size.x), cells(size.y), cells(size.z)];
+
+        let extent = Position::new(
+            dimensions[0] as f32,
+            dimensions[1] as f32,
+            dimensions[2] as f32,
+        ) * cell_size;
+        let center = (min + max) * 0.5;
+
+        Self::new(center - extent * 0.5, cell_size, dimensions)
+    }
+
+    /// Returns the number of cells.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.dimensions.iter().product()
+    }
+
+    /// Returns `true` if the grid has no cells.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the index of the cell with the given coordinates in the sample [`Vec`].
+    #[must_use]
+    pub fn index(&self, [x, y, z]: [usize; 3]) -> usize {
+        x + self.dimensions[0] * (y + self.dimensions[1] * z)
+    }
+
+    /// Returns the coordinates of the cell with the given index in the sample [`Vec`].
+    #[must_use]
+    pub fn coordinates(&self, index: usize) -> [usize; 3] {
+        let [width, height, _] = self.dimensions;
+        [
+            index % width,
+            index / width % height,
+            index / (width * height),
+        ]
+    }
+
+    /// Returns the center of the cell with the given coordinates.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn position(&self, [x, y, z]: [usize; 3]) -> Position {
+        self.origin + Position::new(x as f32 + 0.5, y as f32 + 0.5, z as f32 + 0.5) * self.cell_size
+    }
+}
+
+impl Bvh {
+    /// Evaluates [`Bvh::signed_distance`] at the center of every cell.
+    ///
+    /// All samples are infinite if the mesh has no triangles.
+    #[must_use]
+    pub fn sample_signed_distance(&self, grid: &Grid) -> Vec<f32> {
+        sample(grid, |position| {
+            self.signed_distance(position).unwrap_or(f32::INFINITY)
+        })
+    }
+
+    /// Evaluates [`Bvh::contains`] at the center of every cell.
+    #[must_use]
+    pub fn sample_inside(&self, grid: &Grid) -> Vec<bool> {
+        sample(grid, |position| self.contains(position))
+    }
+}
+
+/// Evaluates the function at the center of every cell.
+fn sample<T, F>(grid: &Grid, function: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(Position) -> T + Sync,
+{
+    #[cfg(feature = "rayon")]
+    let indices = (0..grid.len()).into_par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let indices = 0..grid.len();
+
+    indices
+        .map(|index| function(grid.position(grid.coordinates(index))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives;
+
+    #[test]
+    fn fit() {
+        let grid = Grid::fit(
+            Position::new(-2.0, -1.0, 0.0),
+            Position::new(2.0, 1.0, 0.5),
+            8,
+        );
+        assert_eq!(grid.dimensions, [8, 4, 1]);
+        assert_eq!(grid.len(), 32);
+        assert_eq!(grid.origin, Position::new(-2.0, -1.0, 0.0));
+        assert_eq!(grid.position([0, 0, 0]), Position::new(-1.75, -0.75, 0.25));
+
+        let point = Grid::fit(Position::default(), Position::default(), 0);
+        assert_eq!(point.dimensions, [1, 1, 1]);
+        assert_eq!(point.position([0, 0, 0]), Position::default());
+    }
+
+    #[test]
+    fn coordinates() {
+        let grid = Grid::new(Position::default(), 1.0, [3, 4, 5]);
+        for index in 0..grid.len() {
+            assert_eq!(grid.index(grid.coordinates(index)), index);
+        }
+        assert_eq!(grid.index([1, 2, 3]), 1 + 3 * (2 + 4 * 3));
+        assert!(Grid::new(Position::default(), 1.0, [3, 0, 5]).is_empty());
+    }
+
+    #[test]
+    fn signed_distance() {
+        let mesh = primitives::icosphere(1.0, 3);
+        let bvh = Bvh::new(&mesh).unwrap();
+        let (min, max) = mesh.bounding_box().unwrap();
+        let grid = Grid::fit(min * 1.5, max * 1.5, 12);
+
+        let samples = bvh.sample_signed_distance(&grid);
+        assert_eq!(samples.len(), grid.len());
+
+        for (index, &distance) in samples.iter().enumerate() {
+            let expected = grid.position(grid.coordinates(index)).length() - 1.0;
+            assert!((distance - expected).abs() < 0.02, "{distance} {expected}");
+        }
+    }
+
+    #[test]
+    fn inside() {
+        let bvh = Bvh::new(&primitives::cube(2.0)).unwrap();
+        let grid = Grid::new(Position::new(-2.0, -2.0, -2.0), 1.0, [4, 4, 4]);
+
+        let inside = bvh.sample_inside(&grid);
+        for (index, &inside) in inside.iter().enumerate() {
+            let [x, y, z] = grid.coordinates(index);
+            let expected = [x, y, z].iter().all(|&c| c == 1 || c == 2);
+            assert_eq!(inside, expected, "{:?}", [x, y, z]);
+        }
+
+        let empty = Bvh::new(&crate::geometry::mesh::Mesh::default()).unwrap();
+        assert!(empty
+            .sample_signed_distance(&grid)
+            .iter()
+            .all(|distance| distance.is_infinite()));
+    }
+}
@@ -0,0 +1,259 @@
+use std::io::Write;
+
+use crate::geometry::position::Position;
+
+use super::{error::Error, Facet, Result};
+
+/// The name of the solid written to ASCII `stl` files.
+const SOLID_NAME: &str = "off-rs";
+
+/// Iterates the non-empty lines of ASCII `stl` data split into whitespace separated tokens.
+struct Lines<'a> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    prev_line_index: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            lines: s.lines().enumerate(),
+            prev_line_index: 0,
+        }
+    }
+
+    /// Returns the next line, failing with a message naming the `expected` element at the end of the data.
+    fn expect_line(&mut self, expected: &str) -> Result<(usize, Vec<&'a str>)> {
+        self.next().ok_or_else(|| {
+            Error::ascii(
+                self.prev_line_index + 1,
+                format!("Unexpected end of data (expected: `{expected}`)"),
+            )
+        })
+    }
+
+    /// Consumes the next line and checks that it consists of exactly the given keywords.
+    fn expect_keywords(&mut self, keywords: &[&str]) -> Result<()> {
+        let expected = keywords.join(" ");
+        let (line_index, tokens) = self.expect_line(&expected)?;
+
+        if tokens != keywords {
+            return Err(Error::ascii(
+                line_index,
+                format!(
+                    "Unexpected line (expected: `{expected}`, actual: `{}`)",
+                    tokens.join(" ")
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Consumes the next line, checks that it starts with the given keywords and parses the three following numbers.
+    fn expect_position(&mut self, keywords: &[&str]) -> Result<Position> {
+        let (line_index, tokens) = self.expect_line(&keywords.join(" "))?;
+
+        parse_position(line_index, &tokens, keywords)
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = (usize, Vec<&'a str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (line_index, line) in self.lines.by_ref() {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+
+            if !tokens.is_empty() {
+                self.prev_line_index = line_index;
+                return Some((line_index, tokens));
+            }
+        }
+
+        None
+    }
+}
+
+/// Parses the facets of ASCII `stl` data.
+///
+/// Multiple consecutive solids are merged.
+pub(super) fn parse(bytes: &[u8]) -> Result<Vec<Facet>> {
+    let string = std::str::from_utf8(bytes)
+        .map_err(|err| Error::ascii(0, format!("Data is not valid UTF-8: ({err})")))?;
+
+    let mut lines = Lines::new(string);
+    let mut facets = Vec::new();
+
+    while let Some((line_index, tokens)) = lines.next() {
+        if tokens[0] != "solid" {
+            return Err(Error::ascii(
+                line_index,
+                format!("Expected `solid` (actual: `{}`)", tokens[0]),
+            ));
+        }
+
+        loop {
+            let (line_index, tokens) = lines.expect_line("facet normal` or `endsolid")?;
+
+            match tokens[0] {
+                "endsolid" => break,
+                "facet" => facets.push(parse_facet(&mut lines, line_index, &tokens)?),
+                token => {
+                    return Err(Error::ascii(
+                        line_index,
+                        format!("Expected `facet` or `endsolid` (actual: `{token}`)"),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(facets)
+}
+
+/// Parses a facet from its already consumed `facet normal` line up to and including the `endfacet` line.
+fn parse_facet(lines: &mut Lines<'_>, line_index: usize, tokens: &[&str]) -> Result<Facet> {
+    let normal = parse_position(line_index, tokens, &["facet", "normal"])?;
+
+    lines.expect_keywords(&["outer", "loop"])?;
+    let vertices = [
+        lines.expect_position(&["vertex"])?,
+        lines.expect_position(&["vertex"])?,
+        lines.expect_position(&["vertex"])?,
+    ];
+    lines.expect_keywords(&["endloop"])?;
+    lines.expect_keywords(&["endfacet"])?;
+
+    Ok(Facet {
+        normal,
+        vertices,
+        color: None,
+    })
+}
+
+/// Checks that the tokens start with the given keywords and parses the three following numbers.
+fn parse_position(line_index: usize, tokens: &[&str], keywords: &[&str]) -> Result<Position> {
+    if !tokens.starts_with(keywords) || tokens.len() != keywords.len() + 3 {
+        return Err(Error::ascii(
+            line_index,
+            format!(
+                "Unexpected line (expected: `{} x y z`, actual: `{}`)",
+                keywords.join(" "),
+                tokens.join(" ")
+            ),
+        ));
+    }
+
+    let values = tokens[keywords.len()..]
+        .iter()
+        .map(|s| {
+            s.parse::<f32>().map_err(|err| {
+                Error::ascii(
+                    line_index,
+                    format!("Failed to parse coordinate as number: ({err})"),
+                )
+            })
+        })
+        .collect::<Result<Vec<f32>>>()?;
+
+    Ok(Position::new(values[0], values[1], values[2]))
+}
+
+/// Writes the facets as ASCII `stl` data.
+pub(super) fn write<W: Write>(facets: &[Facet], mut writer: W) -> Result<()> {
+    writeln!(writer, "solid {SOLID_NAME}")?;
+
+    for facet in facets {
+        let n = facet.normal;
+        writeln!(writer, "  facet normal {:e} {:e} {:e}", n.x, n.y, n.z)?;
+        writeln!(writer, "    outer loop")?;
+        for v in &facet.vertices {
+            writeln!(writer, "      vertex {:e} {:e} {:e}", v.x, v.y, v.z)?;
+        }
+        writeln!(writer, "    endloop")?;
+        writeln!(writer, "  endfacet")?;
+    }
+
+    writeln!(writer, "endsolid {SOLID_NAME}")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRIANGLE: &str = "solid test
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 0 1 0
+    endloop
+  endfacet
+endsolid test
+";
+
+    fn facet() -> Facet {
+        Facet {
+            normal: Position::new(0.0, 0.0, 1.0),
+            vertices: [
+                Position::new(0.0, 0.0, 0.0),
+                Position::new(1.0, 0.0, 0.0),
+                Position::new(0.0, 1.0, 0.0),
+            ],
+            color: None,
+        }
+    }
+
+    #[test]
+    fn parse_triangle() {
+        assert_eq!(parse(TRIANGLE.as_bytes()).unwrap(), vec![facet()]);
+    }
+
+    #[test]
+    fn parse_multiple_solids() {
+        let data = format!("{TRIANGLE}\n{TRIANGLE}");
+        assert_eq!(parse(data.as_bytes()).unwrap(), vec![facet(), facet()]);
+    }
+
+    #[test]
+    fn parse_empty_solid() {
+        assert_eq!(parse(b"solid\nendsolid").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn parse_missing_vertex() {
+        let data = TRIANGLE.replace("      vertex 0 1 0\n", "");
+        assert!(matches!(
+            parse(data.as_bytes()),
+            Err(Error::InvalidAscii { line_index: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn parse_invalid_number() {
+        let data = TRIANGLE.replace("vertex 1 0 0", "vertex 1 a 0");
+        assert!(matches!(
+            parse(data.as_bytes()),
+            Err(Error::InvalidAscii { line_index: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn parse_unterminated() {
+        let data = TRIANGLE.replace("endsolid test\n", "");
+        assert!(matches!(
+            parse(data.as_bytes()),
+            Err(Error::InvalidAscii { line_index: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut bytes = Vec::new();
+        write(&[facet()], &mut bytes).unwrap();
+        assert!(bytes.starts_with(b"solid off-rs\n"));
+        assert_eq!(parse(&bytes).unwrap(), vec![facet()]);
+    }
+}
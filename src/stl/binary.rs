@@ -0,0 +1,217 @@
+use std::io::Write;
+
+use crate::geometry::{color::Color, position::Position};
+
+use super::{error::Error, Facet, Result};
+
+/// The size of the header in bytes.
+const HEADER_SIZE: usize = 80;
+
+/// The size of the header and the facet count in bytes.
+const PREAMBLE_SIZE: usize = HEADER_SIZE + 4;
+
+/// The size of a single facet (normal, three vertices and the attribute byte count) in bytes.
+const FACET_SIZE: usize = 50;
+
+/// The header written to binary `stl` files.
+///
+/// It must not start with `solid`, as readers would mistake the file for ASCII `stl`.
+const HEADER: &[u8] = b"Binary STL written by off-rs";
+
+/// The bit of the attribute byte count that marks a valid VisCAM/SolidView color.
+const COLOR_VALID: u16 = 1 << 15;
+
+/// Reads the facet count from the preamble.
+fn facet_count(bytes: &[u8]) -> Option<usize> {
+    let count = bytes.get(HEADER_SIZE..PREAMBLE_SIZE)?;
+
+    usize::try_from(u32::from_le_bytes([count[0], count[1], count[2], count[3]])).ok()
+}
+
+/// Returns whether the length of the data matches the facet count of the preamble.
+pub(super) fn has_consistent_size(bytes: &[u8]) -> bool {
+    facet_count(bytes)
+        .and_then(|count| count.checked_mul(FACET_SIZE))
+        .and_then(|size| size.checked_add(PREAMBLE_SIZE))
+        .is_some_and(|size| size == bytes.len())
+}
+
+/// Parses the facets of binary `stl` data.
+pub(super) fn parse(bytes: &[u8]) -> Result<Vec<Facet>> {
+    let count = facet_count(bytes).ok_or_else(|| {
+        Error::InvalidBinary(
+            format!(
+                "Data is too short to contain a header and facet count (expected: >= {PREAMBLE_SIZE} bytes, actual: {})",
+                bytes.len()
+            )
+            .into(),
+        )
+    })?;
+
+    let body = &bytes[PREAMBLE_SIZE..];
+    let expected = count
+        .checked_mul(FACET_SIZE)
+        .ok_or_else(|| Error::InvalidBinary(format!("Facet count {count} is too large").into()))?;
+
+    // Trailing bytes after the declared facets are ignored, missing bytes are not
+    if body.len() < expected {
+        return Err(Error::InvalidBinary(
+            format!(
+                "Not enough data for {count} facets (expected: {expected} bytes, actual: {})",
+                body.len()
+            )
+            .into(),
+        ));
+    }
+
+    Ok(body
+        .chunks_exact(FACET_SIZE)
+        .take(count)
+        .map(parse_facet)
+        .collect())
+}
+
+/// Parses a single 50 byte facet.
+fn parse_facet(chunk: &[u8]) -> Facet {
+    let float = |offset: usize| {
+        f32::from_le_bytes([
+            chunk[offset],
+            chunk[offset + 1],
+            chunk[offset + 2],
+            chunk[offset + 3],
+        ])
+    };
+    let position =
+        |offset: usize| Position::new(float(offset), float(offset + 4), float(offset + 8));
+    let attribute = u16::from_le_bytes([chunk[48], chunk[49]]);
+
+    Facet {
+        normal: position(0),
+        vertices: [position(12), position(24), position(36)],
+        color: attribute_to_color(attribute),
+    }
+}
+
+/// Writes the facets as binary `stl` data.
+pub(super) fn write<W: Write>(facets: &[Facet], mut writer: W) -> Result<()> {
+    let count = u32::try_from(facets.len()).map_err(|_| {
+        Error::InvalidMesh(
+            format!(
+                "Binary stl supports at most {} facets (actual: {})",
+                u32::MAX,
+                facets.len()
+            )
+            .into(),
+        )
+    })?;
+
+    let mut header = [0; HEADER_SIZE];
+    header[..HEADER.len()].copy_from_slice(HEADER);
+    writer.write_all(&header)?;
+    writer.write_all(&count.to_le_bytes())?;
+
+    for facet in facets {
+        let mut chunk = [0; FACET_SIZE];
+        let positions = std::iter::once(&facet.normal).chain(facet.vertices.iter());
+
+        for (i, position) in positions.enumerate() {
+            for (j, value) in [position.x, position.y, position.z].iter().enumerate() {
+                let offset = i * 12 + j * 4;
+                chunk[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        chunk[48..].copy_from_slice(&color_to_attribute(facet.color).to_le_bytes());
+        writer.write_all(&chunk)?;
+    }
+
+    Ok(())
+}
+
+/// Decodes a VisCAM/SolidView 15 bit color from the attribute byte count.
+fn attribute_to_color(attribute: u16) -> Option<Color> {
+    if attribute & COLOR_VALID == 0 {
+        return None;
+    }
+
+    let channel = |shift: u16| f32::from((attribute >> shift) & 0b1_1111) / 31.0;
+
+    Some(Color {
+        red: channel(10),
+        green: channel(5),
+        blue: channel(0),
+        alpha: 1.0,
+    })
+}
+
+/// Encodes a color as VisCAM/SolidView 15 bit color in the attribute byte count.
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+fn color_to_attribute(color: Option<Color>) -> u16 {
+    let Some(color) = color else {
+        return 0;
+    };
+
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 31.0).round() as u16;
+
+    COLOR_VALID | channel(color.red) << 10 | channel(color.green) << 5 | channel(color.blue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facet() -> Facet {
+        Facet {
+            normal: Position::new(0.0, 0.0, 1.0),
+            vertices: [
+                Position::new(0.0, 0.0, 0.0),
+                Position::new(1.0, 0.0, 0.0),
+                Position::new(0.0, 1.0, 0.0),
+            ],
+            color: Some(Color::new(1.0, 0.0, 0.0, 1.0).unwrap()),
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut bytes = Vec::new();
+        write(&[facet(), facet()], &mut bytes).unwrap();
+        assert_eq!(bytes.len(), PREAMBLE_SIZE + 2 * FACET_SIZE);
+        assert!(!bytes.starts_with(b"solid"));
+        assert!(has_consistent_size(&bytes));
+        assert_eq!(parse(&bytes).unwrap(), vec![facet(), facet()]);
+    }
+
+    #[test]
+    fn truncated() {
+        let mut bytes = Vec::new();
+        write(&[facet()], &mut bytes).unwrap();
+        bytes.pop();
+        assert!(matches!(parse(&bytes), Err(Error::InvalidBinary(_))));
+        assert!(matches!(parse(&[0; 10]), Err(Error::InvalidBinary(_))));
+    }
+
+    #[test]
+    fn trailing_bytes() {
+        let mut bytes = Vec::new();
+        write(&[facet()], &mut bytes).unwrap();
+        bytes.extend_from_slice(&[0; 7]);
+        assert!(!has_consistent_size(&bytes));
+        assert_eq!(parse(&bytes).unwrap(), vec![facet()]);
+    }
+
+    #[test]
+    fn attribute_color() {
+        assert_eq!(attribute_to_color(0), None);
+        assert_eq!(attribute_to_color(0x7fff), None);
+        assert_eq!(color_to_attribute(None), 0);
+        assert_eq!(
+            color_to_attribute(Some(Color::new(1.0, 0.0, 1.0, 0.5).unwrap())),
+            0b1_11111_00000_11111
+        );
+        assert_eq!(
+            attribute_to_color(0b1_00000_11111_00000),
+            Some(Color::new(0.0, 1.0, 0.0, 1.0).unwrap())
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Display, Formatter},
+    io,
+};
+
+/// An error that occured while reading or writing `stl` data.
+#[derive(Debug)]
+pub enum Error {
+    /// An IO error occurred while reading or writing the data.
+    IOError(io::Error),
+    /// The ASCII `stl` data has an invalid format.
+    InvalidAscii {
+        /// The line number in the `stl` string where the error occured.
+        line_index: usize,
+        /// An error message describing the problem.
+        message: Cow<'static, str>,
+    },
+    /// The binary `stl` data has an invalid format.
+    InvalidBinary(Cow<'static, str>),
+    /// The [`Mesh`](`crate::geometry::mesh::Mesh`) cannot be represented as `stl`.
+    InvalidMesh(Cow<'static, str>),
+}
+
+impl Error {
+    /// Creates a new [`Error::InvalidAscii`] with the given line number and message.
+    #[must_use]
+    pub(crate) fn ascii<M: Into<Cow<'static, str>>>(line_index: usize, message: M) -> Self {
+        Self::InvalidAscii {
+            line_index,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::IOError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::IOError(e) => write!(f, "IO Error: {e}"),
+            Self::InvalidAscii {
+                line_index,
+                message,
+            } => write!(f, "InvalidAscii @ ln:{} - {message}", line_index + 1),
+            Self::InvalidBinary(message) => write!(f, "InvalidBinary - {message}"),
+            Self::InvalidMesh(message) => write!(f, "InvalidMesh - {message}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::IOError(e)
+    }
+}
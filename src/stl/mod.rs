@@ -0,0 +1,270 @@
+//! Conversion between [`Mesh`] and the `stl` ([Stereolithography](<https://en.wikipedia.org/wiki/STL_(file_format)>)) format.
+//!
+//! Both the ASCII and the binary variant of `stl` are supported.
+//! Polygon faces are triangulated when writing and duplicate vertices are welded when reading,
+//! so that the resulting [`Mesh`] shares vertices between adjacent faces.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use off_rs::stl::{self, Format};
+//!
+//! let off_string = r#"
+//! OFF
+//! 3 1
+//! 1.0 0.0 0.0
+//! 0.0 1.0 0.0
+//! 0.0 0.0 1.0
+//! 3  0 1 2
+//! "#;
+//!
+//! let mesh = off_rs::parse(off_string, Default::default()).unwrap();
+//!
+//! let mut bytes = Vec::new();
+//! stl::write(&mesh, &mut bytes, Format::Binary).unwrap();
+//!
+//! assert_eq!(stl::parse(&bytes).unwrap(), mesh);
+//! ```
+
+mod ascii;
+mod binary;
+pub mod error;
+
+use crate::{
+    compression,
+    geometry::{builder::MeshBuilder, color::Color, mesh::Mesh, position::Position},
+    parser::options::Limits,
+};
+use std::{collections::HashMap, io::Write, path::Path};
+
+use self::error::Error;
+
+/// This result may contain the parsed [`Mesh`] or the [`Error`] that occurred.
+pub type Result<T = Mesh> = std::result::Result<T, Error>;
+
+/// The different `stl` encodings.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Format {
+    /// Human readable `solid ... endsolid` text.
+    Ascii,
+    /// The compact binary representation with an 80 byte header.
+    #[default]
+    Binary,
+}
+
+/// A single triangle of an `stl` file.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct Facet {
+    pub normal: Position,
+    pub vertices: [Position; 3],
+    pub color: Option<Color>,
+}
+
+/// Detects whether the given bytes contain ASCII or binary `stl` data.
+///
+/// Binary files whose header starts with `solid` are recognized by their facet count matching the data length.
+#[must_use]
+pub fn detect_format(bytes: &[u8]) -> Format {
+    if binary::has_consistent_size(bytes) {
+        Format::Binary
+    } else if bytes.trim_ascii_start().starts_with(b"solid") {
+        Format::Ascii
+    } else {
+        Format::Binary
+    }
+}
+
+/// Parses a [`Mesh`] from ASCII or binary `stl` data.
+///
+/// Vertices with identical positions are welded into a single vertex.
+///
+/// # Errors
+///
+/// Will return [`Error`] if the data is not valid `stl`.
+pub fn parse(bytes: &[u8]) -> Result {
+    let facets = match detect_format(bytes) {
+        Format::Ascii => ascii::parse(bytes)?,
+        Format::Binary => binary::parse(bytes)?,
+    };
+
+    Ok(weld(&facets))
+}
+
+/// Parses a [`Mesh`] from a [`std::path::Path`] pointing to an `stl` file.
+///
+/// Compressed files are decompressed transparently and at most [`Limits::byte_count`] bytes of decompressed data are
+/// read into memory, see [`compression::read_limited`]. The other limits do not apply to `stl` data.
+///
+/// # Errors
+///
+/// Will return [`Error`] if an error occurs while reading the file, if it exceeds the byte limit or if an error occurs
+/// while parsing the `stl` data.
+pub fn from_path<P: AsRef<Path>>(path: P, limits: Limits) -> Result {
+    parse(&compression::read_limited(path, limits.byte_count)?)
+}
+
+/// Writes a [`Mesh`] as `stl` data of the given [`Format`] to `writer`.
+///
+/// Faces are fan triangulated and facet normals are calculated from the vertex positions.
+/// Face colors are stored in the attribute bytes of binary `stl` data using the VisCAM/SolidView convention.
+///
+/// # Errors
+///
+/// Will return [`Error`] if a face references a vertex that does not exist or if writing fails.
+pub fn write<W: Write>(mesh: &Mesh, writer: W, format: Format) -> Result<()> {
+    let facets = facets(mesh)?;
+
+    match format {
+        Format::Ascii => ascii::write(&facets, writer),
+        Format::Binary => binary::write(&facets, writer),
+    }
+}
+
+/// Writes a [`Mesh`] as `stl` data of the given [`Format`] to the file at `path`.
+///
+/// The data is compressed based on the extension of `path`, see [`compression::create`].
+///
+/// # Errors
+///
+/// Will return [`Error`] if a face references a vertex that does not exist or if writing the file fails.
+pub fn to_path<P: AsRef<Path>>(mesh: &Mesh, path: P, format: Format) -> Result<()> {
+    let mut encoder = compression::create(path)?;
+    write(mesh, &mut encoder, format)?;
+    encoder.finish()?.flush()?;
+
+    Ok(())
+}
+
+/// Triangulates the faces of the mesh and calculates the facet normals.
+fn facets(mesh: &Mesh) -> Result<Vec<Facet>> {
+    let position = |face_index: usize, vertex_index: usize| {
+        mesh.vertices
+            .get(vertex_index)
+            .map(|vertex| vertex.position)
+            .ok_or_else(|| {
+                Error::InvalidMesh(
+                    format!(
+                        "Face {face_index} references vertex {vertex_index}, but the mesh only has {} vertices",
+                        mesh.vertex_count()
+                    )
+                    .into(),
+                )
+            })
+    };
+
+    let mut facets = Vec::with_capacity(mesh.face_count());
+
+    for (face_index, face) in mesh.faces.iter().enumerate() {
+        for [a, b, c] in face.triangles() {
+            let vertices = [
+                position(face_index, a)?,
+                position(face_index, b)?,
+                position(face_index, c)?,
+            ];
+            let normal = (vertices[1] - vertices[0])
+                .cross(vertices[2] - vertices[0])
+                .normalized();
+
+            facets.push(Facet {
+                normal,
+                vertices,
+                color: face.color,
+            });
+        }
+    }
+
+    Ok(facets)
+}
+
+/// Builds an indexed [`Mesh`] from the facets by merging vertices with identical positions.
+fn weld(facets: &[Facet]) -> Mesh {
+    // The faces share a single index buffer
+    let mut builder = MeshBuilder::with_capacity(0, facets.len());
+    let mut indices: HashMap<[u32; 3], usize> = HashMap::new();
+
+    for facet in facets {
+        let vertices = facet.vertices.map(|position| {
+            // Adding zero turns `-0.0` into `0.0`, so both are welded into the same vertex
+            let key = [
+                (position.x + 0.0).to_bits(),
+                (position.y + 0.0).to_bits(),
+                (position.z + 0.0).to_bits(),
+            ];
+
+            *indices
+                .entry(key)
+                .or_insert_with(|| builder.add_vertex(position))
+        });
+
+        builder.push_face(vertices, facet.color);
+    }
+
+    builder.build_unchecked()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::mesh::{Face, Vertex};
+
+    fn triangle(z: f32) -> Facet {
+        Facet {
+            normal: Position::new(0.0, 0.0, 1.0),
+            vertices: [
+                Position::new(0.0, 0.0, z),
+                Position::new(1.0, 0.0, 0.0),
+                Position::new(0.0, 1.0, 0.0),
+            ],
+            color: None,
+        }
+    }
+
+    #[test]
+    fn detect() {
+        assert_eq!(detect_format(b"solid test\nendsolid test"), Format::Ascii);
+        assert_eq!(detect_format(b"  solid"), Format::Ascii);
+        assert_eq!(detect_format(&[0; 84]), Format::Binary);
+
+        let mut binary = b"solid but actually binary".to_vec();
+        binary.resize(84, 0);
+        assert_eq!(detect_format(&binary), Format::Binary);
+    }
+
+    #[test]
+    fn weld_shared_vertices() {
+        let mesh = weld(&[triangle(0.0), triangle(-0.0), triangle(1.0)]);
+        assert_eq!(mesh.vertex_count(), 4);
+        assert_eq!(mesh.faces[0].vertices, vec![0, 1, 2]);
+        assert_eq!(mesh.faces[1].vertices, vec![0, 1, 2]);
+        assert_eq!(mesh.faces[2].vertices, vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn facets_of_quad() {
+        let mesh = Mesh {
+            vertices: vec![
+                Vertex::new(Position::new(0.0, 0.0, 0.0), None),
+                Vertex::new(Position::new(1.0, 0.0, 0.0), None),
+                Vertex::new(Position::new(1.0, 1.0, 0.0), None),
+                Vertex::new(Position::new(0.0, 1.0, 0.0), None),
+            ],
+            faces: vec![Face::new(vec![0, 1, 2, 3], None)],
+        };
+
+        let facets = facets(&mesh).unwrap();
+        assert_eq!(facets.len(), 2);
+        assert!(facets
+            .iter()
+            .all(|facet| facet.normal == Position::new(0.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn facets_invalid_index() {
+        let mesh = Mesh {
+            vertices: vec![],
+            faces: vec![Face::new(vec![0, 1, 2], None)],
+        };
+
+        assert!(matches!(facets(&mesh), Err(Error::InvalidMesh(_))));
+    }
+}
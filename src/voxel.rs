@@ -0,0 +1,452 @@
+//! Voxelization of meshes into dense occupancy grids.
+//!
+//! A [`VoxelGrid`] stores one flag per cell of a [`Grid`], it can be exported to the [binvox] format used by most
+//! voxel tools and converted back to a [`Mesh`] made of cube faces.
+//!
+//! [binvox]: https://www.patrickmin.com/binvox/binvox.html
+
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use crate::{
+    bvh::Bvh,
+    geometry::{builder::MeshBuilder, mesh::Mesh, position::Position, validation::Error},
+    sampling::Grid,
+};
+
+/// Defines which cells [`Mesh::voxelize`] marks as occupied.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Hash)]
+pub enum Fill {
+    /// Cells that intersect a face of the mesh.
+    #[default]
+    Surface,
+    /// Cells that intersect a face or whose center lies inside the mesh, which has to be closed.
+    Solid,
+}
+
+/// A dense grid of occupied and empty cells.
+#[derive(Clone, PartialEq, Debug)]
+pub struct VoxelGrid {
+    grid: Grid,
+    occupied: Vec<bool>,
+}
+
+impl VoxelGrid {
+    /// Creates a new [`VoxelGrid`] without any occupied cells.
+    #[must_use]
+    pub fn new(grid: Grid) -> Self {
+        Self {
+            occupied: vec![false; grid.len()],
+            grid,
+        }
+    }
+
+    /// Returns the grid that defines the position and size of the cells.
+    #[must_use]
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Returns the occupancy of all cells in the order of [`Grid::index`].
+    #[must_use]
+    pub fn occupied(&self) -> &[bool] {
+        &self.occupied
+    }
+
+    /// Returns the number of occupied cells.
+    #[must_use]
+    pub fn occupied_count(&self) -> usize {
+        self.occupied.iter().filter(|&&occupied| occupied).count()
+    }
+
+    /// Returns `true` if the cell with the given coordinates is occupied.
+    ///
+    /// Cells outside of the grid are empty.
+    #[must_use]
+    pub fn is_occupied(&self, coordinates: [usize; 3]) -> bool {
+        self.contains(coordinates) && self.occupied[self.grid.index(coordinates)]
+    }
+
+    /// Marks the cell with the given coordinates as occupied or empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinates are outside of the grid.
+    pub fn set(&mut self, coordinates: [usize; 3], occupied: bool) {
+        assert!(
+            self.contains(coordinates),
+            "{coordinates:?} is outside of a grid with dimensions {:?}",
+            self.grid.dimensions
+        );
+
+        let index = self.grid.index(coordinates);
+        self.occupied[index] = occupied;
+    }
+
+    /// Writes the grid in the binvox format.
+    ///
+    /// Binvox grids are cubic, other grids are padded with empty cells along their shorter axes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if writing fails.
+    pub fn write_binvox<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let size = self.grid.dimensions.into_iter().max().unwrap_or(0);
+        let origin = self.grid.origin;
+
+        writeln!(writer, "#binvox 1")?;
+        writeln!(writer, "dim {size} {size} {size}")?;
+        writeln!(writer, "translate {} {} {}", origin.x, origin.y, origin.z)?;
+        #[allow(clippy::cast_precision_loss)]
+        let scale = self.grid.cell_size * size as f32;
+        writeln!(writer, "scale {scale}")?;
+        writeln!(writer, "data")?;
+
+        // The y coordinate changes fastest, followed by z and x
+        let cells = (0..size).flat_map(|x| {
+            (0..size).flat_map(move |z| (0..size).map(move |y| self.is_occupied([x, y, z])))
+        });
+
+        // Run length encoded as pairs of a value and a count of at most 255
+        let mut runs = Vec::new();
+        let mut run: Option<(bool, u8)> = None;
+        for occupied in cells {
+            match &mut run {
+                Some((value, count)) if *value == occupied && *count < u8::MAX => *count += 1,
+                _ => {
+                    if let Some((value, count)) = run {
+                        runs.extend([u8::from(value), count]);
+                    }
+                    run = Some((occupied, 1));
+                }
+            }
+        }
+        if let Some((value, count)) = run {
+            runs.extend([u8::from(value), count]);
+        }
+
+        writer.write_all(&runs)
+    }
+
+    /// Creates a mesh with a quad for every side of an occupied cell that faces an empty cell.
+    ///
+    /// Adjacent quads share their vertices, so the surface of a group of cells is closed and its faces point
+    /// outwards. Cells that only touch along an edge or a corner share these vertices.
+    #[must_use]
+    pub fn to_mesh(&self) -> Mesh {
+        let mut builder = MeshBuilder::new();
+        let mut vertices: HashMap<[usize; 3], usize> = HashMap::new();
+
+        for (index, _) in self
+            .occupied
+            .iter()
+            .enumerate()
+            .filter(|(_, &occupied)| occupied)
+        {
+            let cell = self.grid.coordinates(index);
+
+            for (offset, corners) in SIDES {
+                let neighbour = [0, 1, 2].map(|axis| cell[axis].checked_add_signed(offset[axis]));
+                let exposed = match neighbour {
+                    [Some(x), Some(y), Some(z)] => !self.is_occupied([x, y, z]),
+                    _ => true,
+                };
+                if !exposed {
+                    continue;
+                }
+
+                let face = corners.map(|corner| {
+                    let lattice = [0, 1, 2].map(|axis| cell[axis] + corner[axis]);
+                    *vertices
+                        .entry(lattice)
+                        .or_insert_with(|| builder.add_vertex(self.lattice_position(lattice)))
+                });
+                builder.add_face(face);
+            }
+        }
+
+        builder.build_unchecked()
+    }
+
+    /// Returns `true` if the coordinates are inside of the grid.
+    fn contains(&self, coordinates: [usize; 3]) -> bool {
+        coordinates
+            .iter()
+            .zip(self.grid.dimensions)
+            .all(|(&coordinate, dimension)| coordinate < dimension)
+    }
+
+    /// Returns the position of a corner of the cells.
+    #[allow(clippy::cast_precision_loss)]
+    fn lattice_position(&self, [x, y, z]: [usize; 3]) -> Position {
+        self.grid.origin + Position::new(x as f32, y as f32, z as f32) * self.grid.cell_size
+    }
+}
+
+/// The sides of a cell as the offset to the neighbouring cell and the corners of the side, in counter-clockwise order
+/// when viewed from outside of the cell.
+const SIDES: [([isize; 3], [[usize; 3]; 4]); 6] = [
+    ([0, 0, -1], [[0, 0, 0], [0, 1, 0], [1, 1, 0], [1, 0, 0]]),
+    ([0, 0, 1], [[0, 0, 1], [1, 0, 1], [1, 1, 1], [0, 1, 1]]),
+    ([0, -1, 0], [[0, 0, 0], [1, 0, 0], [1, 0, 1], [0, 0, 1]]),
+    ([0, 1, 0], [[0, 1, 0], [0, 1, 1], [1, 1, 1], [1, 1, 0]]),
+    ([-1, 0, 0], [[0, 0, 0], [0, 0, 1], [0, 1, 1], [0, 1, 0]]),
+    ([1, 0, 0], [[1, 0, 0], [1, 1, 0], [1, 1, 1], [1, 0, 1]]),
+];
+
+impl Mesh {
+    /// Voxelizes the mesh into a grid fitted to its bounding box with `resolution` cells along its longest axis.
+    ///
+    /// See [`Grid::fit`] for the layout of the grid and [`Fill`] for the cells that are occupied. Faces are split into
+    /// triangles like [`Face::triangles`](crate::geometry::mesh::Face::triangles) does. A mesh without vertices results
+    /// in an empty grid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if a face references a vertex that does not exist.
+    pub fn voxelize(&self, resolution: usize, fill: Fill) -> Result<VoxelGrid, Error> {
+        let Some((min, max)) = self.bounding_box() else {
+            return Ok(VoxelGrid::new(Grid::new(Position::default(), 1.0, [0; 3])));
+        };
+        let mut voxels = VoxelGrid::new(Grid::fit(min, max, resolution));
+
+        // The hierarchy validates the faces and splits them into triangles for both passes
+        let bvh = Bvh::new(self)?;
+        for positions in bvh.triangle_positions() {
+            voxels.fill_triangle(positions);
+        }
+
+        if fill == Fill::Solid {
+            let inside = bvh.sample_inside(&voxels.grid);
+            for (occupied, inside) in voxels.occupied.iter_mut().zip(inside) {
+                *occupied |= inside;
+            }
+        }
+
+        Ok(voxels)
+    }
+}
+
+impl VoxelGrid {
+    /// Marks all cells that overlap the triangle as occupied.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn fill_triangle(&mut self, positions: [Position; 3]) {
+        let grid = self.grid;
+        let cell = |position: Position| {
+            let relative = (position - grid.origin) * (1.0 / grid.cell_size);
+            [relative.x, relative.y, relative.z]
+        };
+
+        let corners = positions.map(cell);
+        let range = |axis: usize| {
+            let values = corners.map(|corner| corner[axis]);
+            let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+            let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            let last = grid.dimensions[axis].saturating_sub(1);
+
+            // Casting saturates negative values to zero
+            (
+                (min.floor() as usize).min(last),
+                (max.floor() as usize).min(last),
+            )
+        };
+        let ranges = [range(0), range(1), range(2)];
+
+        // Slightly enlarge the cells so faces on a cell boundary are found by the cells on both sides
+        let half_size = grid.cell_size * 0.5 * (1.0 + 1e-4);
+
+        for z in ranges[2].0..=ranges[2].1 {
+            for y in ranges[1].0..=ranges[1].1 {
+                for x in ranges[0].0..=ranges[0].1 {
+                    let center = grid.position([x, y, z]);
+                    if overlaps(positions.map(|position| position - center), half_size) {
+                        let index = grid.index([x, y, z]);
+                        self.occupied[index] = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns `true` if the triangle overlaps the cube with the given half size centered at the origin.
+///
+/// Uses the separating axis test by Akenine-Möller, with the axes of the cube, the normal of the triangle and the
+/// cross products of the axes of the cube with the edges of the triangle.
+fn overlaps(triangle: [Position; 3], half_size: f32) -> bool {
+    let edges = [
+        triangle[1] - triangle[0],
+        triangle[2] - triangle[1],
+        triangle[0] - triangle[2],
+    ];
+    let axes = [
+        Position::new(1.0, 0.0, 0.0),
+        Position::new(0.0, 1.0, 0.0),
+        Position::new(0.0, 0.0, 1.0),
+    ];
+
+    let separates = |axis: Position| {
+        let projections = triangle.map(|position| position.dot(axis));
+        let min = projections.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = projections
+            .iter()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max);
+        let radius = half_size * (axis.x.abs() + axis.y.abs() + axis.z.abs());
+
+        min > radius || max < -radius
+    };
+
+    let normal = edges[0].cross(edges[1]);
+
+    !(axes.into_iter().any(separates)
+        || separates(normal)
+        || axes
+            .iter()
+            .flat_map(|axis| edges.map(|edge| axis.cross(edge)))
+            .any(separates))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::mesh::Face, primitives};
+    use std::collections::HashSet;
+
+    #[test]
+    fn surface() {
+        let voxels = primitives::cube(2.0).voxelize(4, Fill::Surface).unwrap();
+        assert_eq!(voxels.grid().dimensions, [4, 4, 4]);
+        assert_eq!(voxels.grid().origin, Position::new(-1.0, -1.0, -1.0));
+
+        // Only the outer layer touches the faces
+        assert_eq!(voxels.occupied_count(), 64 - 8);
+        assert!(!voxels.is_occupied([1, 2, 1]));
+        assert!(voxels.is_occupied([0, 2, 1]));
+        assert!(!voxels.is_occupied([4, 0, 0]));
+    }
+
+    #[test]
+    fn solid() {
+        let voxels = primitives::cube(2.0).voxelize(4, Fill::Solid).unwrap();
+        assert_eq!(voxels.occupied_count(), 64);
+        let voxels = primitives::cube(1e-4).voxelize(4, Fill::Solid).unwrap();
+        assert_eq!(voxels.occupied_count(), 64);
+
+        let sphere = primitives::icosphere(1.0, 3)
+            .voxelize(20, Fill::Solid)
+            .unwrap();
+        let grid = sphere.grid();
+        assert_eq!(grid.dimensions, [20, 20, 20]);
+
+        // Cells well inside or outside of the sphere, the cells in between belong to the surface
+        for (index, &occupied) in sphere.occupied().iter().enumerate() {
+            let distance = grid.position(grid.coordinates(index)).length();
+            if distance < 0.85 {
+                assert!(occupied, "{distance}");
+            } else if distance > 1.1 {
+                assert!(!occupied, "{distance}");
+            }
+        }
+    }
+
+    #[test]
+    fn thin_shapes() {
+        let voxels = primitives::plane(4.0, 1.0, 1, 1)
+            .voxelize(8, Fill::Surface)
+            .unwrap();
+        assert_eq!(voxels.grid().dimensions, [8, 2, 1]);
+        assert_eq!(voxels.occupied_count(), 16);
+
+        assert_eq!(
+            Mesh::default()
+                .voxelize(8, Fill::Solid)
+                .unwrap()
+                .occupied_count(),
+            0
+        );
+    }
+
+    #[test]
+    fn to_mesh() {
+        let mut voxels = VoxelGrid::new(Grid::new(Position::default(), 0.5, [3, 2, 2]));
+        voxels.set([0, 0, 0], true);
+
+        let mesh = voxels.to_mesh();
+        assert_eq!((mesh.vertex_count(), mesh.face_count()), (8, 6));
+        assert_eq!(
+            mesh.bounding_box(),
+            Some((Position::default(), Position::new(0.5, 0.5, 0.5)))
+        );
+
+        voxels.set([1, 0, 0], true);
+        let mesh = voxels.to_mesh();
+        assert_eq!((mesh.vertex_count(), mesh.face_count()), (12, 10));
+
+        // The surface is closed and points outwards
+        let mut edges = HashSet::new();
+        for face in &mesh.faces {
+            for i in 0..4 {
+                assert!(edges.insert((face.vertices[i], face.vertices[(i + 1) % 4])));
+            }
+        }
+        assert!(edges.iter().all(|&(a, b)| edges.contains(&(b, a))));
+        assert!(mesh.face_normal(0).unwrap().z < 0.0);
+
+        let bvh = Bvh::new(&mesh).unwrap();
+        assert!(bvh.contains(Position::new(0.75, 0.25, 0.25)));
+        assert!(!bvh.contains(Position::new(1.25, 0.25, 0.25)));
+    }
+
+    #[test]
+    fn round_trip() {
+        let cube = primitives::cube(2.0);
+        let mesh = cube.voxelize(4, Fill::Solid).unwrap().to_mesh();
+
+        assert_eq!(mesh.face_count(), 6 * 16);
+        assert_eq!(mesh.bounding_box(), cube.bounding_box());
+    }
+
+    #[test]
+    fn binvox() {
+        let mut voxels = VoxelGrid::new(Grid::new(Position::new(1.0, 2.0, 3.0), 0.5, [2, 1, 1]));
+        voxels.set([0, 0, 0], true);
+        voxels.set([1, 0, 0], true);
+
+        let mut bytes = Vec::new();
+        voxels.write_binvox(&mut bytes).unwrap();
+
+        let header = "#binvox 1\ndim 2 2 2\ntranslate 1 2 3\nscale 1\ndata\n";
+        assert_eq!(&bytes[..header.len()], header.as_bytes());
+        assert_eq!(&bytes[header.len()..], [1, 1, 0, 3, 1, 1, 0, 3]);
+
+        // Runs are split after 255 cells
+        let mut bytes = Vec::new();
+        VoxelGrid::new(Grid::new(Position::default(), 1.0, [7, 7, 7]))
+            .write_binvox(&mut bytes)
+            .unwrap();
+        assert!(bytes.ends_with(&[0, 255, 0, 88]));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of a grid")]
+    fn set_outside() {
+        VoxelGrid::new(Grid::new(Position::default(), 1.0, [2, 2, 2])).set([0, 2, 0], true);
+    }
+
+    #[test]
+    fn vertex_out_of_range() {
+        let mut mesh = primitives::cube(1.0);
+        mesh.faces[1] = Face::new([0, 9, 2], None);
+        assert_eq!(
+            mesh.voxelize(4, Fill::Surface).unwrap_err(),
+            Error::VertexOutOfRange {
+                face: 1,
+                vertex: 9,
+                vertex_count: 8
+            }
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use std::io::{self, Write};
+
+use crate::{
+    geometry::{color::Color, mesh::Mesh},
+    parser::color_format::ColorFormat,
+};
+
+/// Writes a [`crate::geometry::mesh::Mesh`] as `off` data.
+#[derive(Debug, Clone, Copy)]
+pub struct Writer<'a> {
+    mesh: &'a Mesh,
+    color_format: ColorFormat,
+}
+
+impl<'a> Writer<'a> {
+    /// Creates a new [`Writer`] which writes colors in the given [`ColorFormat`].
+    #[must_use]
+    pub fn new(mesh: &'a Mesh, color_format: ColorFormat) -> Self {
+        Self { mesh, color_format }
+    }
+
+    /// Writes the `off` data to `writer`.
+    ///
+    /// The output can be read back by the [`Parser`](`crate::parser::Parser`) using the same [`ColorFormat`].
+    ///
+    /// # Errors
+    ///
+    /// Will return [`io::Error`] if writing fails or if a color cannot be represented in the [`ColorFormat`].
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "OFF")?;
+        writeln!(
+            writer,
+            "{} {} {}",
+            self.mesh.vertex_count(),
+            self.mesh.face_count(),
+            self.mesh.edge_count()
+        )?;
+
+        for vertex in &self.mesh.vertices {
+            let position = vertex.position;
+            write!(writer, "{} {} {}", position.x, position.y, position.z)?;
+            self.write_color(&mut writer, vertex.color)?;
+            writeln!(writer)?;
+        }
+
+        for face in &self.mesh.faces {
+            write!(writer, "{}", face.vertices.len())?;
+            for index in &face.vertices {
+                write!(writer, " {index}")?;
+            }
+            if let Some(color_index) = face.color_index {
+                write!(writer, " {color_index}")?;
+            } else {
+                self.write_color(&mut writer, face.color)?;
+            }
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the `off` data to a [`String`].
+    ///
+    /// # Errors
+    ///
+    /// Will return [`io::Error`] if a color cannot be represented in the [`ColorFormat`].
+    pub fn write_string(&self) -> io::Result<String> {
+        let mut bytes = Vec::new();
+        self.write(&mut bytes)?;
+
+        // The writer only ever emits ASCII
+        String::from_utf8(bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Writes the color channels prefixed by a space, if a color is given.
+    fn write_color<W: Write>(&self, writer: &mut W, color: Option<Color>) -> io::Result<()> {
+        let Some(color) = color else {
+            return Ok(());
+        };
+
+        let channel_count = self.color_format.channel_count();
+
+        if self.color_format.is_float() {
+            for channel in Vec::<f32>::from(color).iter().take(channel_count) {
+                write!(writer, " {channel}")?;
+            }
+        } else {
+            let channels = Vec::<u8>::try_from(color)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+            for channel in channels.iter().take(channel_count) {
+                write!(writer, " {channel}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        geometry::{
+            colormap::Colormap,
+            mesh::{Face, Vertex},
+            position::Position,
+        },
+        parser::{options::Options, Parser},
+    };
+
+    fn mesh() -> Mesh {
+        Mesh {
+            vertices: vec![
+                Vertex::new(Position::new(1.5, 0.0, 0.0), None),
+                Vertex::new(
+                    Position::new(0.0, -1.0, 0.0),
+                    Some(Color::new(1.0, 0.0, 0.0, 1.0).unwrap()),
+                ),
+                Vertex::new(Position::new(0.0, 0.0, 1e-7), None),
+            ],
+            faces: vec![Face::new(
+                vec![0, 1, 2],
+                Some(Color::new(0.0, 1.0, 0.0, 1.0).unwrap()),
+            )],
+        }
+    }
+
+    #[test]
+    fn write_float() {
+        let string = Writer::new(&mesh(), ColorFormat::RGBAFloat)
+            .write_string()
+            .unwrap();
+
+        assert_eq!(
+            string,
+            "OFF\n3 1 2\n1.5 0 0\n0 -1 0 1 0 0 1\n0 0 0.0000001\n3 0 1 2 0 1 0 1\n"
+        );
+    }
+
+    #[test]
+    fn write_integer() {
+        let string = Writer::new(&mesh(), ColorFormat::RGBInteger)
+            .write_string()
+            .unwrap();
+
+        assert!(string.contains("0 -1 0 255 0 0\n"));
+        assert!(string.contains("3 0 1 2 0 255 0\n"));
+    }
+
+    #[test]
+    fn write_invalid_color() {
+        let mut mesh = mesh();
+        mesh.faces[0].color = Some(Color {
+            red: 2.0,
+            green: 0.0,
+            blue: 0.0,
+            alpha: 1.0,
+        });
+
+        assert!(Writer::new(&mesh, ColorFormat::RGBInteger)
+            .write_string()
+            .is_err());
+    }
+
+    #[test]
+    fn write_color_index() {
+        let mut mesh = mesh();
+        mesh.faces[0] = Face::with_color_index(vec![0, 1, 2], 7, &Colormap::default());
+
+        let string = Writer::new(&mesh, ColorFormat::RGBAFloat)
+            .write_string()
+            .unwrap();
+        assert!(string.ends_with("3 0 1 2 7\n"));
+        assert_eq!(
+            Parser::new(&string, Options::default()).parse().unwrap(),
+            mesh
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        for color_format in [
+            ColorFormat::RGBFloat,
+            ColorFormat::RGBAFloat,
+            ColorFormat::RGBInteger,
+            ColorFormat::RGBAInteger,
+            ColorFormat::Float,
+            ColorFormat::Integer,
+        ] {
+            let string = Writer::new(&mesh(), color_format).write_string().unwrap();
+            let options = Options {
+                color_format,
+                ..Options::default()
+            };
+
+            assert_eq!(Parser::new(&string, options).parse().unwrap(), mesh());
+        }
+    }
+}
@@ -0,0 +1,163 @@
+#![cfg(feature = "cli")]
+
+use off_rs::{
+    compression::{self, Compression},
+    parser::color_format::ColorFormat,
+    stl,
+};
+use std::{
+    fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/cube.off");
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("off-rs-cli-{}-{name}", std::process::id()))
+}
+
+fn off(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_off"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn validate() {
+    let output = off(&["validate", PATH]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).ends_with(": valid (8 vertices, 6 faces)\n"));
+    assert!(stderr(&output).is_empty());
+}
+
+#[test]
+fn validate_invalid() {
+    let path = temp_path("invalid.off");
+    fs::write(&path, "OFF\n3 1 0\n0 0 0\n1 x 0\n0 1 0\n3 0 1 2\n").unwrap();
+    let output = off(&["validate", path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).is_empty());
+
+    let stderr = stderr(&output);
+    assert!(stderr.starts_with(&format!("{}: Parser Error:", path.display())));
+    assert_eq!(stderr.lines().count(), 1);
+}
+
+#[test]
+fn unreadable_files() {
+    let output = off(&["validate", "cube.txt"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("cube.txt: unsupported file extension"));
+
+    let path = temp_path("missing.off");
+    let output = off(&["info", path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with(&path.display().to_string()));
+}
+
+#[test]
+fn usage_errors() {
+    assert_eq!(off(&[]).status.code(), Some(2));
+    assert_eq!(off(&["validate"]).status.code(), Some(2));
+    assert_eq!(
+        off(&["validate", PATH, "--max-bytes", "-1"]).status.code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn info() {
+    let output = off(&["info", PATH]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let text = stdout(&output);
+    for line in [
+        "Format:        OFF",
+        "Vertices:      8",
+        "Faces:         6",
+        "Face colors:   6 of 6",
+        "Color format:  Float",
+    ] {
+        assert!(text.contains(line), "{line}");
+    }
+
+    let mesh = off_rs::from_path(PATH, Default::default()).unwrap();
+    let path = temp_path("cube.stl");
+    stl::to_path(&mesh, &path, stl::Format::Ascii).unwrap();
+    let output = off(&["info", path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("Format:        STL (ascii)"));
+}
+
+#[test]
+fn max_bytes() {
+    let size = fs::metadata(PATH).unwrap().len().to_string();
+    assert_eq!(
+        off(&["validate", PATH, "--max-bytes", &size]).status.code(),
+        Some(0)
+    );
+
+    let limit = (fs::metadata(PATH).unwrap().len() - 1).to_string();
+    let output = off(&["validate", PATH, "--max-bytes", &limit]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains(&format!("exceeds the limit of {limit} bytes")));
+
+    let output = off(&["validate", PATH, "--max-bytes", "16", "--no-limits"]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn max_bytes_of_decompressed_data() {
+    if !Compression::Gzip.is_supported() {
+        return;
+    }
+
+    // Compresses to a few kilobytes, but decompresses to 64 MiB
+    let path = temp_path("bomb.off.gz");
+    let mut encoder = compression::create(&path).unwrap();
+    encoder.write_all(b"OFF\n").unwrap();
+    for _ in 0..1024 {
+        encoder.write_all(&[b' '; 64 * 1024]).unwrap();
+    }
+    encoder.finish().unwrap();
+
+    let output_path = temp_path("bomb.stl");
+    let output = off(&["info", path.to_str().unwrap(), "--max-bytes", "1024"]);
+    let converted = off(&[
+        "convert",
+        path.to_str().unwrap(),
+        output_path.to_str().unwrap(),
+        "--max-bytes",
+        "1024",
+    ]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("exceeds the limit of 1024 bytes"));
+    assert_eq!(converted.status.code(), Some(1));
+    assert!(!output_path.exists());
+
+    // Compressed files within the limit are decompressed
+    let mesh = off_rs::from_path(PATH, Default::default()).unwrap();
+    let path = temp_path("cube.off.gz");
+    off_rs::to_path(&mesh, &path, ColorFormat::RGBAFloat).unwrap();
+    let output = off(&["validate", path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(output.status.code(), Some(0));
+}
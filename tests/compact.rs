@@ -0,0 +1,74 @@
+use off_rs::{
+    geometry::{compact::CompactMesh, mesh::Mesh},
+    parser::{color_format::ColorFormat, error::Kind, options::Options},
+    Error,
+};
+
+const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/cube.off");
+
+fn options() -> Options {
+    Options {
+        color_format: ColorFormat::Auto,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn same_as_parse() {
+    let off = std::fs::read_to_string(PATH).unwrap();
+
+    let mesh = off_rs::parse(&off, options()).unwrap();
+    let compact = off_rs::parse_compact(&off, options()).unwrap();
+
+    assert_eq!(compact, CompactMesh::try_from(&mesh).unwrap());
+    assert_eq!(Mesh::from(&compact), mesh);
+    assert_eq!(compact.vertex_count(), mesh.vertex_count());
+    assert_eq!(compact.face_count(), mesh.face_count());
+}
+
+#[test]
+fn mixed_colors() {
+    let off = "OFF\n4 3\n0 0 0\n1 0 0 1 0 0\n0 1 0\n0 0 1\n3 0 1 2\n3 0 2 3 7\n3 1 2 3 0 0 255\n";
+
+    let mesh = off_rs::parse(off, options()).unwrap();
+    let compact = off_rs::parse_compact(off, options()).unwrap();
+
+    assert!(!compact.vertex_colors().unwrap().is_complete());
+    assert_eq!(compact.face_color_indices().unwrap().get(1), Some(7));
+    assert_eq!(Mesh::from(compact), mesh);
+}
+
+#[test]
+fn without_colors() {
+    let off = "OFF\n3 1\n0 0 0\n1 0 0\n0 1 0\n3 0 1 2\n";
+    let compact = off_rs::parse_compact(off, options()).unwrap();
+
+    assert_eq!(compact.vertex_colors(), None);
+    assert_eq!(compact.face_colors(), None);
+    assert_eq!(compact.indices(), &[0, 1, 2]);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn index_overflow() {
+    let off = "OFF\n3 1\n0 0 0\n1 0 0\n0 1 0\n3 0 1 4294967296\n";
+
+    assert!(off_rs::parse(off, options()).is_ok());
+    assert!(matches!(
+        off_rs::parse_compact(off, options()),
+        Err(Error::ParserError(err)) if err.kind == Kind::IndexOverflow && err.line_index == 5
+    ));
+}
+
+#[test]
+fn same_error_as_parse() {
+    let off = "OFF\n3 1\n0 0 0\n1 0 0\n0 1\n3 0 1 2\n";
+
+    let (Err(Error::ParserError(compact_error)), Err(Error::ParserError(error))) = (
+        off_rs::parse_compact(off, options()),
+        off_rs::parse(off, options()),
+    ) else {
+        panic!("expected parser errors");
+    };
+    assert_eq!(compact_error, error);
+}
@@ -0,0 +1,157 @@
+use off_rs::{
+    compression::{self, Compression},
+    parser::{
+        color_format::ColorFormat,
+        error::Details,
+        options::{Limit, Limits, Options},
+    },
+    stl, Error,
+};
+use std::{fs, io, path::PathBuf};
+
+const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/cube.off");
+
+const COMPRESSIONS: [(Compression, &str); 3] = [
+    (Compression::Gzip, "gz"),
+    (Compression::Zstd, "zst"),
+    (Compression::Bzip2, "bz2"),
+];
+
+fn options() -> Options {
+    Options {
+        color_format: ColorFormat::RGBAFloat,
+        ..Default::default()
+    }
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("off-rs-compression-{}-{name}", std::process::id()))
+}
+
+#[test]
+fn off_roundtrip() {
+    let mesh = off_rs::from_path(PATH, options()).unwrap();
+
+    for (compression, extension) in COMPRESSIONS {
+        let path = temp_path(&format!("cube.off.{extension}"));
+        let written = off_rs::to_path(&mesh, &path, ColorFormat::RGBAFloat);
+
+        if !compression.is_supported() {
+            assert!(
+                matches!(written, Err(Error::IOError(err)) if err.kind() == io::ErrorKind::Unsupported)
+            );
+            assert!(!path.exists());
+            continue;
+        }
+
+        written.unwrap();
+        let bytes = fs::read(&path).unwrap();
+        let read = off_rs::from_path(&path, options());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(Compression::detect(&bytes), compression);
+        assert_eq!(read.unwrap(), mesh);
+    }
+}
+
+#[test]
+fn detect_by_magic_bytes() {
+    let mesh = off_rs::from_path(PATH, options()).unwrap();
+
+    for (compression, extension) in COMPRESSIONS {
+        if !compression.is_supported() {
+            continue;
+        }
+
+        // Write compressed data to a file without a compression extension
+        let compressed = temp_path(&format!("magic.off.{extension}"));
+        off_rs::to_path(&mesh, &compressed, ColorFormat::RGBAFloat).unwrap();
+        let path = temp_path(&format!("magic-{extension}.off"));
+        fs::rename(&compressed, &path).unwrap();
+
+        let read = off_rs::from_path(&path, options());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(read.unwrap(), mesh);
+    }
+}
+
+#[test]
+fn unsupported_compression() {
+    let magic_bytes: [&[u8]; 3] = [&[0x1F, 0x8B], &[0x28, 0xB5, 0x2F, 0xFD], b"BZh9"];
+
+    for ((compression, extension), magic_bytes) in COMPRESSIONS.into_iter().zip(magic_bytes) {
+        if compression.is_supported() {
+            continue;
+        }
+
+        let path = temp_path(&format!("unsupported.off.{extension}"));
+        fs::write(&path, magic_bytes).unwrap();
+        let result = off_rs::from_path(&path, options());
+        fs::remove_file(&path).unwrap();
+
+        assert!(
+            matches!(result, Err(Error::IOError(err)) if err.kind() == io::ErrorKind::Unsupported)
+        );
+    }
+}
+
+#[test]
+fn stl_roundtrip() {
+    let mesh = off_rs::from_path(PATH, options()).unwrap();
+    let expected = stl::parse(&{
+        let mut bytes = Vec::new();
+        stl::write(&mesh, &mut bytes, stl::Format::Binary).unwrap();
+        bytes
+    })
+    .unwrap();
+
+    for (compression, extension) in COMPRESSIONS {
+        if !compression.is_supported() {
+            continue;
+        }
+
+        let path = temp_path(&format!("cube.stl.{extension}"));
+        stl::to_path(&mesh, &path, stl::Format::Binary).unwrap();
+        let bytes = compression::read(&path).unwrap();
+        let read = stl::from_path(&path, Limits::default());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(stl::detect_format(&bytes), stl::Format::Binary);
+        assert_eq!(read.unwrap(), expected);
+    }
+}
+
+#[test]
+fn byte_count_limit_of_decompressed_data() {
+    let mesh = off_rs::from_path(PATH, options()).unwrap();
+    let size = off_rs::to_string(&mesh, ColorFormat::RGBAFloat)
+        .unwrap()
+        .len();
+
+    for (compression, extension) in COMPRESSIONS {
+        if !compression.is_supported() {
+            continue;
+        }
+
+        let path = temp_path(&format!("limit.off.{extension}"));
+        off_rs::to_path(&mesh, &path, ColorFormat::RGBAFloat).unwrap();
+
+        let mut limited = options();
+        limited.limits.byte_count = size - 1;
+        let read = off_rs::from_path(&path, limited);
+        fs::remove_file(&path).unwrap();
+
+        let Err(Error::ParserError(err)) = read else {
+            panic!("expected a parser error, got {read:?}");
+        };
+        assert_eq!(
+            err.details,
+            Details::LimitExceeded {
+                limit: Limit::ByteCount,
+                max: size - 1,
+                actual: size,
+            }
+        );
+    }
+}
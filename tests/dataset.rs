@@ -0,0 +1,113 @@
+use off_rs::{
+    dataset::{self, Layout, Split, Summary},
+    parser::{color_format::ColorFormat, error::Kind, options::Options},
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const CUBE: &str = include_str!("../examples/cube.off");
+
+fn options() -> Options {
+    Options {
+        color_format: ColorFormat::RGBAFloat,
+        ..Default::default()
+    }
+}
+
+/// Creates a small dataset with the ModelNet layout and returns its root.
+fn create_dataset(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("off-rs-dataset-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+
+    let files = [
+        ("airplane/train/airplane_0001.off", CUBE),
+        ("airplane/train/airplane_0002.off", CUBE),
+        ("airplane/test/airplane_0003.off", "OFF\n1 0\n0 0 x\n"),
+        ("chair/test/chair_0001.off", CUBE),
+        ("chair/test/chair_0002.off", ""),
+        ("chair/test/readme.txt", "not a mesh"),
+    ];
+
+    for (path, contents) in files {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    root
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap()
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[test]
+fn load_model_net() {
+    let root = create_dataset("load");
+    let entries = dataset::load(&root, options(), &Layout::Directory).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    let paths: Vec<String> = entries
+        .iter()
+        .map(|entry| relative(&root, &entry.path))
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "airplane/test/airplane_0003.off",
+            "airplane/train/airplane_0001.off",
+            "airplane/train/airplane_0002.off",
+            "chair/test/chair_0001.off",
+            "chair/test/chair_0002.off",
+        ]
+    );
+
+    assert_eq!(entries[0].category.as_deref(), Some("airplane"));
+    assert_eq!(entries[0].split, Some(Split::Test));
+    assert_eq!(entries[1].split, Some(Split::Train));
+    assert_eq!(entries[3].category.as_deref(), Some("chair"));
+    assert!(entries[1].result.is_ok());
+
+    let summary = Summary::from_entries(&entries);
+    assert_eq!(summary.file_count, 5);
+    assert_eq!(summary.loaded_count, 3);
+    assert_eq!(summary.failed_count(), 2);
+    assert_eq!(summary.io_error_count, 0);
+    assert_eq!(
+        summary.parser_errors.get(&Kind::InvalidVertexPosition),
+        Some(&1)
+    );
+    assert_eq!(summary.parser_errors.get(&Kind::Empty), Some(&1));
+    assert_eq!(summary.categories.get("airplane"), Some(&3));
+    assert_eq!(summary.categories.get("chair"), Some(&2));
+}
+
+#[test]
+fn missing_root() {
+    assert!(dataset::load("does/not/exist", options(), &Layout::Directory).is_err());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn load_parallel() {
+    let root = create_dataset("parallel");
+    let serial = dataset::load(&root, options(), &Layout::ParentDirectory).unwrap();
+    let parallel = dataset::load_parallel(&root, options(), &Layout::ParentDirectory).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(serial.len(), parallel.len());
+    for (serial, parallel) in serial.iter().zip(&parallel) {
+        assert_eq!(serial.path, parallel.path);
+        assert_eq!(serial.category, parallel.category);
+        assert_eq!(serial.result.as_ref().ok(), parallel.result.as_ref().ok());
+    }
+    assert_eq!(
+        Summary::from_entries(&serial),
+        Summary::from_entries(&parallel)
+    );
+}
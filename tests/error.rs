@@ -1,7 +1,12 @@
 use off_rs::{
-    parser::{color_format::ColorFormat, options::Options},
+    parser::{
+        color_format::ColorFormat,
+        error::{Cause, Details, Element, Kind},
+        options::{Limit, Limits, Options},
+    },
     Error,
 };
+use std::{fs, path::Path};
 
 #[test]
 fn missing_vertex_color() {
@@ -27,7 +32,151 @@ OFF
         Error::ParserError(off_rs::parser::error::Error {
             kind: off_rs::parser::error::Kind::InvalidColor,
             line_index: 4,
-            message: _
+            ..
         })
     ));
 }
+
+#[test]
+fn missing_vertex_color_details() {
+    let off_string = "OFF\n3 1 0\n0 0 0 12 122 210\n1 0 0 34 112\n0 1 0 1 2 3\n3 0 1 2\n";
+
+    let Err(Error::ParserError(error)) = off_rs::parse(
+        off_string,
+        Options {
+            color_format: ColorFormat::RGBInteger,
+            ..Default::default()
+        },
+    ) else {
+        panic!("expected a parser error");
+    };
+
+    assert_eq!(
+        error.details,
+        Details::UnexpectedCount {
+            element: Element::ColorChannel,
+            expected: 3..=3,
+            actual: 2,
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "InvalidColor @ ln:4 - Invalid number of color elements given (expected: 3, actual: 2)"
+    );
+}
+
+#[test]
+fn missing_faces_details() {
+    let Err(Error::ParserError(error)) = off_rs::parse(
+        "OFF\n3 2\n0 0 0\n1 0 0\n0 1 0\n3 0 1 2\n",
+        Options::default(),
+    ) else {
+        panic!("expected a parser error");
+    };
+
+    assert_eq!(
+        error.details,
+        Details::UnexpectedCount {
+            element: Element::Face,
+            expected: 2..=2,
+            actual: 1,
+        }
+    );
+}
+
+#[test]
+fn source_chain() {
+    let Err(err) = off_rs::parse("OFF\n1 0\n0 0 x\n", Options::default()) else {
+        panic!("expected an error");
+    };
+
+    // The parser error is part of the message, so the chain continues with its cause
+    let cause = std::error::Error::source(&err).unwrap();
+    assert!(cause.is::<std::num::ParseFloatError>());
+    assert!(cause.source().is_none());
+    assert_eq!(err.path(), None);
+
+    let messages = std::iter::successors(Some(&err as &dyn std::error::Error), |e| e.source())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        [
+            "Parser Error: InvalidVertexPosition @ ln:3 - Failed to parse coordinate as number: (invalid float literal)",
+            "invalid float literal"
+        ]
+    );
+}
+
+#[test]
+fn color_source() {
+    let Err(Error::ParserError(err)) =
+        off_rs::parse("OFF\n1 0\n0 0 0 2.0 0 0 1\n", Options::default())
+    else {
+        panic!("expected a parser error");
+    };
+
+    assert!(matches!(err.cause(), Some(Cause::Color(_))));
+    assert_eq!(
+        err.details,
+        Details::InvalidToken {
+            element: Element::ColorChannel,
+            token: "2.0".to_string(),
+        }
+    );
+}
+
+#[test]
+fn path_of_io_error() {
+    let err = off_rs::from_path("does/not/exist.off", Options::default()).unwrap_err();
+
+    assert!(matches!(&err, Error::IOError(io) if io.kind() == std::io::ErrorKind::NotFound));
+    assert_eq!(err.path(), Some(Path::new("does/not/exist.off")));
+    assert!(err.to_string().contains("does/not/exist.off"));
+}
+
+#[test]
+fn path_of_parser_error() {
+    let path = std::env::temp_dir().join(format!("off-rs-error-{}.off", std::process::id()));
+    fs::write(&path, "OFF\n1 0\n0 0 x\n").unwrap();
+    let err = off_rs::from_path(&path, Options::default()).unwrap_err();
+    fs::remove_file(&path).unwrap();
+
+    let Error::ParserError(parser_error) = &err else {
+        panic!("expected a parser error");
+    };
+    assert_eq!(parser_error.kind, Kind::InvalidVertexPosition);
+    assert_eq!(err.path(), Some(path.as_path()));
+    assert!(parser_error
+        .to_string()
+        .starts_with(&format!("{}: InvalidVertexPosition @ ln:3", path.display())));
+}
+
+#[test]
+fn byte_count_limit_of_file() {
+    let path = std::env::temp_dir().join(format!("off-rs-limit-{}.off", std::process::id()));
+    fs::write(&path, "OFF\n1 0\n0 0 0\n").unwrap();
+    let options = Options {
+        limits: Limits {
+            byte_count: 8,
+            ..Limits::default()
+        },
+        ..Options::default()
+    };
+    let err = off_rs::from_path(&path, options).unwrap_err();
+    fs::remove_file(&path).unwrap();
+
+    let Error::ParserError(parser_error) = &err else {
+        panic!("expected a parser error");
+    };
+    assert_eq!(parser_error.kind, Kind::LimitExceeded);
+    assert_eq!(
+        parser_error.details,
+        Details::LimitExceeded {
+            limit: Limit::ByteCount,
+            max: 8,
+            actual: 9,
+        }
+    );
+    assert_eq!(err.path(), Some(path.as_path()));
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "mmap")]
+
+use off_rs::{
+    parser::{color_format::ColorFormat, options::Options},
+    Error,
+};
+use std::{fs, path::PathBuf};
+
+const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/cube.off");
+
+fn options() -> Options {
+    Options {
+        color_format: ColorFormat::RGBAFloat,
+        ..Default::default()
+    }
+}
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("off-rs-mmap-{}-{name}", std::process::id()));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn same_as_from_path() {
+    assert_eq!(
+        off_rs::from_path_mmap(PATH, options()).unwrap(),
+        off_rs::from_path(PATH, options()).unwrap()
+    );
+}
+
+#[test]
+fn empty_file() {
+    let path = temp_file("empty.off", b"");
+    let result = off_rs::from_path_mmap(&path, options());
+    fs::remove_file(path).unwrap();
+
+    assert!(matches!(result, Err(Error::ParserError(_))));
+}
+
+#[test]
+fn invalid_utf8() {
+    let path = temp_file("invalid.off", b"OFF\n\xff\xfe");
+    let result = off_rs::from_path_mmap(&path, options());
+    fs::remove_file(path).unwrap();
+
+    assert!(matches!(result, Err(Error::IOError(_))));
+}
+
+#[test]
+fn missing_file() {
+    assert!(matches!(
+        off_rs::from_path_mmap("does/not/exist.off", options()),
+        Err(Error::IOError(_))
+    ));
+}
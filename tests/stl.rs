@@ -0,0 +1,80 @@
+use off_rs::{
+    geometry::mesh::Mesh,
+    parser::{
+        color_format::ColorFormat,
+        options::{Limits, Options},
+    },
+    stl::{self, Format},
+};
+use std::{fs, io};
+
+const CUBE: &str = include_str!("../examples/cube.off");
+
+fn cube() -> Mesh {
+    off_rs::parse(
+        CUBE,
+        Options {
+            color_format: ColorFormat::RGBAFloat,
+            ..Default::default()
+        },
+    )
+    .unwrap()
+}
+
+#[test]
+fn binary_roundtrip() {
+    let mut bytes = Vec::new();
+    stl::write(&cube(), &mut bytes, Format::Binary).unwrap();
+    assert_eq!(stl::detect_format(&bytes), Format::Binary);
+    assert_eq!(bytes.len(), 84 + 12 * 50);
+
+    let mesh = stl::parse(&bytes).unwrap();
+    assert_eq!(mesh.vertex_count(), 8);
+    assert_eq!(mesh.face_count(), 12);
+    assert!(mesh.faces.iter().all(|face| face.vertices.len() == 3));
+    assert!(mesh.faces.iter().all(|face| face.color.is_some()));
+
+    // Welding restores the original vertex positions
+    for vertex in &mesh.vertices {
+        assert!(cube()
+            .vertices
+            .iter()
+            .any(|original| original.position == vertex.position));
+    }
+}
+
+#[test]
+fn ascii_roundtrip() {
+    let mut bytes = Vec::new();
+    stl::write(&cube(), &mut bytes, Format::Ascii).unwrap();
+    assert_eq!(stl::detect_format(&bytes), Format::Ascii);
+
+    let mesh = stl::parse(&bytes).unwrap();
+    assert_eq!(mesh.vertex_count(), 8);
+    assert_eq!(mesh.face_count(), 12);
+    assert!(mesh.faces.iter().all(|face| face.color.is_none()));
+
+    let mut again = Vec::new();
+    stl::write(&mesh, &mut again, Format::Ascii).unwrap();
+    assert_eq!(stl::parse(&again).unwrap(), mesh);
+}
+
+#[test]
+fn byte_count_limit() {
+    let path = std::env::temp_dir().join(format!("off-rs-stl-{}-limit.stl", std::process::id()));
+    stl::to_path(&cube(), &path, Format::Binary).unwrap();
+    let size = 84 + 12 * 50;
+
+    let limits = |byte_count| Limits {
+        byte_count,
+        ..Limits::default()
+    };
+    let read = stl::from_path(&path, limits(size));
+    let exceeded = stl::from_path(&path, limits(size - 1));
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(read.unwrap().face_count(), 12);
+    assert!(
+        matches!(exceeded, Err(stl::error::Error::IOError(err)) if err.kind() == io::ErrorKind::InvalidData)
+    );
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "tokio")]
+
+use off_rs::{
+    parser::{
+        color_format::ColorFormat,
+        error::Details,
+        options::{Limit, Options},
+    },
+    Error,
+};
+use std::fs;
+
+const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/cube.off");
+
+fn options() -> Options {
+    Options {
+        color_format: ColorFormat::RGBAFloat,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn same_as_from_path() {
+    assert_eq!(
+        off_rs::from_path_async(PATH, options()).await.unwrap(),
+        off_rs::from_path(PATH, options()).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn from_async_reader() {
+    let bytes = fs::read(PATH).unwrap();
+
+    assert_eq!(
+        off_rs::from_async_reader(&bytes[..], options())
+            .await
+            .unwrap(),
+        off_rs::from_path(PATH, options()).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn same_error_as_parse() {
+    let off = "OFF\n3 1\n0 0 0\n1 0 0\n0 1\n3 0 1 2\n";
+
+    let (Err(Error::ParserError(async_error)), Err(Error::ParserError(error))) = (
+        off_rs::from_async_reader(off.as_bytes(), options()).await,
+        off_rs::parse(off, options()),
+    ) else {
+        panic!("expected parser errors");
+    };
+    assert_eq!(async_error, error);
+}
+
+#[tokio::test]
+async fn invalid_utf8() {
+    assert!(matches!(
+        off_rs::from_async_reader(&b"OFF\n\xff\xfe"[..], options()).await,
+        Err(Error::IOError(_))
+    ));
+}
+
+#[tokio::test]
+async fn missing_file() {
+    assert!(matches!(
+        off_rs::from_path_async("does/not/exist.off", options()).await,
+        Err(Error::IOError(_))
+    ));
+}
+
+#[tokio::test]
+async fn byte_count_limit() {
+    let off = "OFF\n1 0\n0 0 0\n";
+    let mut options = options();
+    options.limits.byte_count = 8;
+
+    let Err(Error::ParserError(err)) = off_rs::from_async_reader(off.as_bytes(), options).await
+    else {
+        panic!("expected a parser error");
+    };
+    assert_eq!(
+        err.details,
+        Details::LimitExceeded {
+            limit: Limit::ByteCount,
+            max: 8,
+            actual: 9,
+        }
+    );
+}
+
+#[tokio::test]
+async fn byte_count_limit_of_endless_reader() {
+    let mut options = options();
+    options.limits.byte_count = 1024;
+
+    assert!(matches!(
+        off_rs::from_async_reader(tokio::io::repeat(b' '), options).await,
+        Err(Error::ParserError(_))
+    ));
+}
@@ -4,7 +4,8 @@ use off_rs::{
         mesh::{Face, Mesh, Vertex},
         position::Position,
     },
-    parser::{color_format::ColorFormat, options::Options},
+    parser::{color_format::ColorFormat, options::Options, Parser},
+    primitives::{self, Platonic},
 };
 
 #[test]
@@ -129,58 +130,64 @@ OFF
             ],
             faces: vec![
                 Face {
-                    vertices: vec![0, 1, 2, 3,],
+                    vertices: vec![0, 1, 2, 3,].into(),
                     color: Some(Color {
                         red: 1.0,
                         green: 0.0,
                         blue: 0.0,
                         alpha: 0.75,
                     },),
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![7, 4, 0, 3,],
+                    vertices: vec![7, 4, 0, 3,].into(),
                     color: Some(Color {
                         red: 0.3,
                         green: 0.4,
                         blue: 0.0,
                         alpha: 0.75,
                     },),
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![4, 5, 1, 0,],
+                    vertices: vec![4, 5, 1, 0,].into(),
                     color: Some(Color {
                         red: 0.2,
                         green: 0.5,
                         blue: 0.1,
                         alpha: 0.75,
                     },),
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![5, 6, 2, 1,],
+                    vertices: vec![5, 6, 2, 1,].into(),
                     color: Some(Color {
                         red: 0.1,
                         green: 0.6,
                         blue: 0.2,
                         alpha: 0.75,
                     },),
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![3, 2, 6, 7,],
+                    vertices: vec![3, 2, 6, 7,].into(),
                     color: Some(Color {
                         red: 0.0,
                         green: 0.7,
                         blue: 0.3,
                         alpha: 0.75,
                     },),
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![6, 5, 4, 7,],
+                    vertices: vec![6, 5, 4, 7,].into(),
                     color: Some(Color {
                         red: 0.0,
                         green: 1.0,
                         blue: 0.0,
                         alpha: 0.75,
                     },),
+                    color_index: None,
                 },
             ],
         }
@@ -288,58 +295,64 @@ OFF
             ],
             faces: vec![
                 Face {
-                    vertices: vec![0, 1, 2, 3,],
+                    vertices: vec![0, 1, 2, 3,].into(),
                     color: Some(Color {
                         red: 1.0,
                         green: 0.0,
                         blue: 0.0,
                         alpha: 1.0,
                     },),
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![7, 4, 0, 3,],
+                    vertices: vec![7, 4, 0, 3,].into(),
                     color: Some(Color {
                         red: 0.0,
                         green: 1.0,
                         blue: 0.0,
                         alpha: 1.0,
                     },),
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![4, 5, 1, 0,],
+                    vertices: vec![4, 5, 1, 0,].into(),
                     color: Some(Color {
                         red: 0.0,
                         green: 0.0,
                         blue: 1.0,
                         alpha: 1.0,
                     },),
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![5, 6, 2, 1,],
+                    vertices: vec![5, 6, 2, 1,].into(),
                     color: Some(Color {
                         red: 0.0,
                         green: 1.0,
                         blue: 0.0,
                         alpha: 1.0,
                     },),
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![3, 2, 6, 7,],
+                    vertices: vec![3, 2, 6, 7,].into(),
                     color: Some(Color {
                         red: 0.0,
                         green: 0.0,
                         blue: 1.0,
                         alpha: 1.0,
                     },),
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![6, 5, 4, 7,],
+                    vertices: vec![6, 5, 4, 7,].into(),
                     color: Some(Color {
                         red: 1.0,
                         green: 0.0,
                         blue: 0.0,
                         alpha: 1.0,
                     },),
+                    color_index: None,
                 },
             ],
         }
@@ -439,34 +452,65 @@ OFF
             ],
             faces: vec![
                 Face {
-                    vertices: vec![0, 1, 3, 2],
+                    vertices: vec![0, 1, 3, 2].into(),
                     color: None,
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![2, 3, 5, 4],
+                    vertices: vec![2, 3, 5, 4].into(),
                     color: None,
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![4, 5, 7, 6],
+                    vertices: vec![4, 5, 7, 6].into(),
                     color: None,
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![6, 7, 1, 0],
+                    vertices: vec![6, 7, 1, 0].into(),
                     color: None,
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![1, 7, 5, 3],
+                    vertices: vec![1, 7, 5, 3].into(),
                     color: None,
+                    color_index: None,
                 },
                 Face {
-                    vertices: vec![6, 0, 2, 4],
+                    vertices: vec![6, 0, 2, 4].into(),
                     color: None,
+                    color_index: None,
                 },
             ],
         }
     );
 }
 
+#[test]
+fn primitives() {
+    let meshes = [
+        primitives::cube(1.0),
+        primitives::platonic(Platonic::Tetrahedron, 1.0),
+        primitives::platonic(Platonic::Octahedron, 1.0),
+        primitives::platonic(Platonic::Dodecahedron, 1.0),
+        primitives::platonic(Platonic::Icosahedron, 1.0),
+        primitives::uv_sphere(1.0, 8, 4),
+        primitives::icosphere(1.0, 1),
+        primitives::cylinder(1.0, 2.0, 6),
+        primitives::cone(1.0, 2.0, 6),
+        primitives::torus(1.0, 0.25, 8, 4),
+        primitives::plane(2.0, 1.0, 3, 2),
+    ];
+
+    for mesh in meshes {
+        let off_string = off_rs::to_string(&mesh, ColorFormat::default()).unwrap();
+        assert_eq!(
+            off_rs::parse(&off_string, Default::default()).unwrap(),
+            mesh
+        );
+    }
+}
+
 #[test]
 fn vertex_colors() {
     let off_string = r#"
@@ -533,9 +577,43 @@ OFF
                 },
             ],
             faces: vec![Face {
-                vertices: vec![0, 1, 2,],
+                vertices: vec![0, 1, 2,].into(),
                 color: None,
+                color_index: None,
             },],
         }
     )
 }
+
+#[test]
+fn auto_color_format() {
+    let examples = [
+        (
+            include_str!("../benches/resources/wiki.off"),
+            ColorFormat::RGBInteger,
+        ),
+        (
+            include_str!("../benches/resources/prinston.off"),
+            ColorFormat::RGBAFloat,
+        ),
+        (include_str!("../examples/cube.off"), ColorFormat::RGBAFloat),
+    ];
+
+    for (off_string, expected) in examples {
+        let auto = Options {
+            color_format: ColorFormat::Auto,
+            ..Default::default()
+        };
+        let explicit = Options {
+            color_format: expected,
+            ..Default::default()
+        };
+
+        let (mesh, detected) = Parser::new(&off_string, auto)
+            .parse_with_color_format()
+            .unwrap();
+
+        assert_eq!(detected, expected);
+        assert_eq!(mesh, off_rs::parse(off_string, explicit).unwrap());
+    }
+}